@@ -0,0 +1,109 @@
+use std::error::Error;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Reason {
+    Cancelled,
+    TimedOut,
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Cancelled => write!(f, "cancelled"),
+            Self::TimedOut => write!(f, "timed out"),
+        }
+    }
+}
+
+// A solver that was stopped early, along with how far it got.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Interrupted<P> {
+    pub reason: Reason,
+    pub progress: P,
+}
+
+impl<P: fmt::Display> fmt::Display for Interrupted<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (progress: {})", self.reason, self.progress)
+    }
+}
+
+impl<P: fmt::Debug + fmt::Display> Error for Interrupted<P> {}
+
+// Checked by long-running solvers between units of work. Clones share the same
+// cancellation flag, so one can be handed to the solver and the other kept to cancel it.
+#[derive(Clone, Default, Debug)]
+pub struct Token {
+    cancelled: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+}
+
+impl Token {
+    // only stops when cancelled
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Self {
+            cancelled: Arc::default(),
+            deadline: Instant::now().checked_add(timeout),
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn check(&self) -> Result<(), Reason> {
+        if self.cancelled.load(Ordering::Relaxed) {
+            return Err(Reason::Cancelled);
+        }
+
+        match self.deadline {
+            Some(deadline) if Instant::now() >= deadline => Err(Reason::TimedOut),
+            _ => Ok(()),
+        }
+    }
+
+    // `check`, reporting `progress` if the solver has to stop
+    pub fn check_at<P>(&self, progress: P) -> Result<(), Interrupted<P>> {
+        self.check()
+            .map_err(|reason| Interrupted { reason, progress })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancel() {
+        let token = Token::new();
+        let handle = token.clone();
+        assert_eq!(token.check(), Ok(()));
+
+        handle.cancel();
+        assert_eq!(token.check(), Err(Reason::Cancelled));
+        assert_eq!(
+            token.check_at(3).map_err(|it| it.to_string()),
+            Err("cancelled (progress: 3)".to_string())
+        );
+    }
+
+    #[test]
+    fn test_timeout() {
+        assert_eq!(
+            Token::with_timeout(Duration::ZERO).check(),
+            Err(Reason::TimedOut)
+        );
+        assert_eq!(Token::with_timeout(Duration::from_hours(1)).check(), Ok(()));
+        assert_eq!(Token::with_timeout(Duration::MAX).check(), Ok(()));
+    }
+}
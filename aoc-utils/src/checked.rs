@@ -0,0 +1,77 @@
+use num::{CheckedAdd, CheckedMul, CheckedSub, Integer, One, Zero};
+use std::error::Error;
+use std::fmt;
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Overflow {
+    pub op: &'static str,
+}
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "arithmetic overflow in {}", self.op)
+    }
+}
+
+impl Error for Overflow {}
+
+pub fn add<T: CheckedAdd>(lhs: &T, rhs: &T) -> Result<T, Overflow> {
+    lhs.checked_add(rhs).ok_or(Overflow { op: "addition" })
+}
+
+pub fn sub<T: CheckedSub>(lhs: &T, rhs: &T) -> Result<T, Overflow> {
+    lhs.checked_sub(rhs).ok_or(Overflow { op: "subtraction" })
+}
+
+pub fn mul<T: CheckedMul>(lhs: &T, rhs: &T) -> Result<T, Overflow> {
+    lhs.checked_mul(rhs).ok_or(Overflow {
+        op: "multiplication",
+    })
+}
+
+pub fn sum<T, I>(iter: I) -> Result<T, Overflow>
+where
+    T: CheckedAdd + Zero,
+    I: IntoIterator<Item = T>,
+{
+    iter.into_iter()
+        .try_fold(T::zero(), |acc, it| add(&acc, &it))
+}
+
+pub fn product<T, I>(iter: I) -> Result<T, Overflow>
+where
+    T: CheckedMul + One,
+    I: IntoIterator<Item = T>,
+{
+    iter.into_iter()
+        .try_fold(T::one(), |acc, it| mul(&acc, &it))
+}
+
+pub fn lcm<T: Integer + CheckedMul + Clone>(lhs: &T, rhs: &T) -> Result<T, Overflow> {
+    if lhs.is_zero() || rhs.is_zero() {
+        return Ok(T::zero());
+    }
+
+    mul(&(lhs.clone() / lhs.gcd(rhs)), rhs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overflow() {
+        assert_eq!(add(&u64::MAX, &1), Err(Overflow { op: "addition" }));
+        assert_eq!(sum([1u8, 2, 3]), Ok(6));
+        assert!(sum([200u8, 100]).is_err());
+        assert_eq!(product([u64::MAX, 1]), Ok(u64::MAX));
+        assert!(product([u64::MAX, 2]).is_err());
+    }
+
+    #[test]
+    fn test_lcm() {
+        assert_eq!(lcm(&4usize, &6), Ok(12));
+        assert_eq!(lcm(&0usize, &6), Ok(0));
+        assert!(lcm(&(u64::MAX - 1), &(u64::MAX - 2)).is_err());
+    }
+}
@@ -0,0 +1,193 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Cycle {
+    pub prefix: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    // maps iteration `n` onto the first iteration with the same state
+    #[must_use]
+    pub const fn reduce(&self, n: usize) -> usize {
+        if n < self.prefix {
+            n
+        } else {
+            self.prefix + (n - self.prefix) % self.period
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CycleHistory<T> {
+    pub cycle: Cycle,
+    history: Vec<T>,
+}
+
+impl<T> CycleHistory<T> {
+    #[must_use]
+    pub fn state_at(&self, n: usize) -> &T {
+        &self.history[self.cycle.reduce(n)]
+    }
+
+    #[must_use]
+    pub fn states(&self) -> &[T] {
+        &self.history
+    }
+}
+
+fn hash_of<T: Hash>(state: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    state.hash(&mut hasher);
+    hasher.finish()
+}
+
+// Keeps every state until the first repetition, but only hashes them once.
+pub fn find_cycle<T, F>(initial: T, mut step: F) -> CycleHistory<T>
+where
+    T: Eq + Hash,
+    F: FnMut(&T) -> T,
+{
+    let mut index: HashMap<u64, Vec<usize>> = HashMap::from([(hash_of(&initial), vec![0])]);
+    let mut history = vec![initial];
+
+    loop {
+        let next = step(&history[history.len() - 1]);
+
+        let seen = index.entry(hash_of(&next)).or_default();
+        if let Some(&prefix) = seen.iter().find(|&&idx| history[idx] == next) {
+            let period = history.len() - prefix;
+            return CycleHistory {
+                cycle: Cycle { prefix, period },
+                history,
+            };
+        }
+
+        seen.push(history.len());
+        history.push(next);
+    }
+}
+
+fn find_prefix<T, F>(initial: &T, step: &F, period: usize) -> usize
+where
+    T: Clone + Eq,
+    F: Fn(&T) -> T,
+{
+    let mut tortoise = initial.clone();
+    let mut hare = advance(initial.clone(), step, period);
+
+    let mut prefix = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        prefix += 1;
+    }
+
+    prefix
+}
+
+pub fn floyd<T, F>(initial: &T, step: F) -> Cycle
+where
+    T: Clone + Eq,
+    F: Fn(&T) -> T,
+{
+    let mut tortoise = step(initial);
+    let mut hare = step(&step(initial));
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&step(&hare));
+    }
+
+    let mut period = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        period += 1;
+    }
+
+    let prefix = find_prefix(initial, &step, period);
+    Cycle { prefix, period }
+}
+
+pub fn brent<T, F>(initial: &T, step: F) -> Cycle
+where
+    T: Clone + Eq,
+    F: Fn(&T) -> T,
+{
+    let mut power = 1;
+    let mut period = 1;
+
+    let mut tortoise = initial.clone();
+    let mut hare = step(initial);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+
+        hare = step(&hare);
+        period += 1;
+    }
+
+    let prefix = find_prefix(initial, &step, period);
+    Cycle { prefix, period }
+}
+
+pub fn advance<T, F>(initial: T, step: F, n: usize) -> T
+where
+    F: Fn(&T) -> T,
+{
+    (0..n).fold(initial, |state, _| step(&state))
+}
+
+// Replays at most `prefix + period` steps to produce the state after `n` iterations.
+pub fn state_at<T, F>(initial: T, step: F, cycle: Cycle, n: usize) -> T
+where
+    F: Fn(&T) -> T,
+{
+    advance(initial, step, cycle.reduce(n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    const fn step(x: &u64) -> u64 {
+        (*x * *x + 1) % 255
+    }
+
+    #[test]
+    fn test_detectors_agree() {
+        for start in 0..255 {
+            let history = find_cycle(start, step);
+            assert_eq!(floyd(&start, step), history.cycle);
+            assert_eq!(brent(&start, step), history.cycle);
+        }
+
+        let history = find_cycle(3, step);
+        assert_eq!(
+            history.cycle,
+            Cycle {
+                prefix: 2,
+                period: 6
+            }
+        );
+    }
+
+    #[test]
+    fn test_state_at() {
+        let start = 3;
+        let history = find_cycle(start, step);
+        let cycle = brent(&start, step);
+
+        for n in 0..100 {
+            let expected = advance(start, step, n);
+            assert_eq!(*history.state_at(n), expected);
+            assert_eq!(state_at(start, step, cycle, n), expected);
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use crate::checked::{self, Overflow};
+use num::Integer;
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Orientation {
+    Clockwise,
+    CounterClockwise,
+    Degenerate,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Location {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+// Simple polygon on the integer lattice, given by its (x, y) vertices.
+// The last vertex is implicitly connected to the first one.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Polygon {
+    vertices: Vec<(i64, i64)>,
+}
+
+impl Polygon {
+    #[must_use]
+    pub const fn new(vertices: Vec<(i64, i64)>) -> Self {
+        Self { vertices }
+    }
+
+    #[must_use]
+    pub fn vertices(&self) -> &[(i64, i64)] {
+        &self.vertices
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = ((i64, i64), (i64, i64))> + '_ {
+        self.vertices
+            .iter()
+            .zip(self.vertices.iter().cycle().skip(1))
+            .map(|(&from, &to)| (from, to))
+    }
+
+    // shoelace formula; positive for counter-clockwise vertices with the y axis pointing up
+    pub fn doubled_signed_area(&self) -> Result<i64, Overflow> {
+        self.edges().try_fold(0, |acc, ((x, y), (xn, yn))| {
+            let cross = checked::sub(&checked::mul(&x, &yn)?, &checked::mul(&xn, &y)?)?;
+            checked::add(&acc, &cross)
+        })
+    }
+
+    // lattice polygons can have half-integer areas, so this is kept doubled as well
+    pub fn doubled_area(&self) -> Result<u64, Overflow> {
+        Ok(self.doubled_signed_area()?.unsigned_abs())
+    }
+
+    pub fn orientation(&self) -> Result<Orientation, Overflow> {
+        Ok(match self.doubled_signed_area()?.signum() {
+            1 => Orientation::CounterClockwise,
+            -1 => Orientation::Clockwise,
+            _ => Orientation::Degenerate,
+        })
+    }
+
+    pub fn boundary_points(&self) -> Result<u64, Overflow> {
+        self.edges().try_fold(0, |acc, ((x, y), (xn, yn))| {
+            let dx = checked::sub(&xn, &x)?.unsigned_abs();
+            let dy = checked::sub(&yn, &y)?.unsigned_abs();
+            checked::add(&acc, &dx.gcd(&dy))
+        })
+    }
+
+    // Pick's theorem: A = I + B/2 - 1. A polygon that doubles back on itself counts some
+    // boundary twice, which would make I negative; it has no interior, so that is 0.
+    pub fn interior_points(&self) -> Result<u64, Overflow> {
+        let doubled_area = checked::add(&self.doubled_area()?, &2)?;
+        Ok(doubled_area.saturating_sub(self.boundary_points()?) / 2)
+    }
+
+    #[must_use]
+    pub fn locate(&self, (px, py): (i64, i64)) -> Location {
+        let mut inside = false;
+        // differences of two i64 may not fit one
+        let (px, py) = (i128::from(px), i128::from(py));
+
+        for ((x, y), (xn, yn)) in self.edges() {
+            let (x, y, xn, yn) = (i128::from(x), i128::from(y), i128::from(xn), i128::from(yn));
+            let cross = (xn - x) * (py - y) - (yn - y) * (px - x);
+
+            if cross == 0
+                && x.min(xn) <= px
+                && px <= x.max(xn)
+                && y.min(yn) <= py
+                && py <= y.max(yn)
+            {
+                return Location::Boundary;
+            }
+
+            // cast a ray towards +x and count the edges it crosses
+            if (y > py) != (yn > py) {
+                let (dx, dy) = (xn - x, yn - y);
+                let lhs = (px - x) * dy;
+                let rhs = (py - y) * dx;
+
+                if (dy > 0 && lhs < rhs) || (dy < 0 && lhs > rhs) {
+                    inside = !inside;
+                }
+            }
+        }
+
+        if inside {
+            Location::Inside
+        } else {
+            Location::Outside
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_square() {
+        let square = Polygon::new(vec![(0, 0), (4, 0), (4, 4), (0, 4)]);
+        assert_eq!(square.doubled_area(), Ok(32));
+        assert_eq!(square.orientation(), Ok(Orientation::CounterClockwise));
+        assert_eq!(square.boundary_points(), Ok(16));
+        assert_eq!(square.interior_points(), Ok(9));
+
+        let reversed = Polygon::new(square.vertices().iter().rev().copied().collect());
+        assert_eq!(reversed.orientation(), Ok(Orientation::Clockwise));
+        assert_eq!(reversed.interior_points(), Ok(9));
+    }
+
+    #[test]
+    fn test_degenerate_and_huge() {
+        // `U 2`, `D 2`: a line walked there and back
+        let line = Polygon::new(vec![(0, 0), (0, 2), (0, 0)]);
+        assert_eq!(line.orientation(), Ok(Orientation::Degenerate));
+        assert_eq!(line.boundary_points(), Ok(4));
+        assert_eq!(line.interior_points(), Ok(0));
+
+        let huge = Polygon::new(vec![(0, 0), (i64::MAX, 0), (i64::MAX, i64::MAX)]);
+        assert_eq!(
+            huge.doubled_area(),
+            Err(Overflow {
+                op: "multiplication"
+            })
+        );
+        let wide = Polygon::new(vec![(i64::MIN, 0), (i64::MAX, 0)]);
+        assert!(wide.boundary_points().is_err());
+        assert_eq!(wide.locate((0, 0)), Location::Boundary);
+        assert_eq!(wide.locate((i64::MIN, 1)), Location::Outside);
+        assert_eq!(wide.locate((i64::MAX, -1)), Location::Outside);
+    }
+
+    #[test]
+    fn test_locate_matches_pick() {
+        let polygon = Polygon::new(vec![(0, 0), (6, 0), (6, 2), (2, 2), (2, 4), (6, 4), (3, 7)]);
+
+        let mut inside = 0;
+        let mut boundary = 0;
+        for x in -1..=8 {
+            for y in -1..=8 {
+                match polygon.locate((x, y)) {
+                    Location::Inside => inside += 1,
+                    Location::Boundary => boundary += 1,
+                    Location::Outside => {}
+                }
+            }
+        }
+
+        assert_eq!(Ok(inside), polygon.interior_points());
+        assert_eq!(Ok(boundary), polygon.boundary_points());
+        assert_eq!(polygon.locate((1, 6)), Location::Outside);
+        assert_eq!(polygon.locate((3, 3)), Location::Outside);
+        assert_eq!(polygon.locate((1, 1)), Location::Inside);
+    }
+}
@@ -0,0 +1,240 @@
+use crate::label::{Label, TooLong};
+use std::collections::{HashMap, VecDeque};
+
+pub type NodeId = u32;
+
+#[derive(Clone, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Interner {
+    ids: HashMap<Label, NodeId>,
+    labels: Vec<Label>,
+}
+
+impl Interner {
+    pub fn intern(&mut self, label: &str) -> Result<NodeId, TooLong> {
+        Ok(self.intern_label(Label::new(label)?))
+    }
+
+    pub fn intern_label(&mut self, label: Label) -> NodeId {
+        if let Some(&id) = self.ids.get(&label) {
+            return id;
+        }
+
+        let id = self.labels.len() as NodeId;
+        self.ids.insert(label, id);
+        self.labels.push(label);
+        id
+    }
+
+    #[must_use]
+    pub fn get(&self, label: &str) -> Option<NodeId> {
+        self.ids.get(&Label::new(label).ok()?).copied()
+    }
+
+    #[must_use]
+    pub fn label(&self, id: NodeId) -> &str {
+        self.labels[id as usize].as_str()
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+}
+
+fn bfs_by<I, F>(start: NodeId, visited: &mut [bool], next: F) -> Vec<NodeId>
+where
+    I: IntoIterator<Item = NodeId>,
+    F: Fn(NodeId) -> I,
+{
+    let mut result = vec![];
+
+    visited[start as usize] = true;
+    let mut queue = VecDeque::from([start]);
+    while let Some(cur) = queue.pop_front() {
+        result.push(cur);
+
+        for n in next(cur) {
+            if !visited[n as usize] {
+                visited[n as usize] = true;
+                queue.push_back(n);
+            }
+        }
+    }
+
+    result
+}
+
+// directed graph over interned labels; edges keep their insertion order
+#[derive(Clone, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Graph {
+    labels: Interner,
+    edges: Vec<Vec<NodeId>>,
+    reverse: Vec<Vec<NodeId>>,
+}
+
+impl Graph {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_node(&mut self, label: &str) -> Result<NodeId, TooLong> {
+        Ok(self.add_label(Label::new(label)?))
+    }
+
+    pub fn add_label(&mut self, label: Label) -> NodeId {
+        let id = self.labels.intern_label(label);
+        if id as usize == self.edges.len() {
+            self.edges.push(vec![]);
+            self.reverse.push(vec![]);
+        }
+
+        id
+    }
+
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId) {
+        self.edges[from as usize].push(to);
+        self.reverse[to as usize].push(from);
+    }
+
+    pub fn add_labelled_edge(&mut self, from: &str, to: &str) -> Result<(), TooLong> {
+        let from = self.add_node(from)?;
+        let to = self.add_node(to)?;
+        self.add_edge(from, to);
+        Ok(())
+    }
+
+    pub fn remove_edge(&mut self, from: NodeId, to: NodeId) {
+        self.edges[from as usize].retain(|&it| it != to);
+        self.reverse[to as usize].retain(|&it| it != from);
+    }
+
+    #[must_use]
+    pub fn id(&self, label: &str) -> Option<NodeId> {
+        self.labels.get(label)
+    }
+
+    #[must_use]
+    pub fn label(&self, id: NodeId) -> &str {
+        self.labels.label(id)
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = NodeId> {
+        (0..self.len()).map(|it| it as NodeId)
+    }
+
+    #[must_use]
+    pub fn successors(&self, id: NodeId) -> &[NodeId] {
+        &self.edges[id as usize]
+    }
+
+    #[must_use]
+    pub fn predecessors(&self, id: NodeId) -> &[NodeId] {
+        &self.reverse[id as usize]
+    }
+
+    // successors and predecessors, i.e. the edges as if they were undirected
+    pub fn neighbours(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.successors(id)
+            .iter()
+            .chain(self.predecessors(id).iter())
+            .copied()
+    }
+
+    // all nodes reachable from `start` along directed edges, in BFS order
+    #[must_use]
+    pub fn bfs(&self, start: NodeId) -> Vec<NodeId> {
+        let mut visited = vec![false; self.len()];
+        bfs_by(start, &mut visited, |it| {
+            self.successors(it).iter().copied()
+        })
+    }
+
+    // weakly connected components
+    #[must_use]
+    pub fn components(&self) -> Vec<Vec<NodeId>> {
+        let mut visited = vec![false; self.len()];
+
+        self.nodes().fold(vec![], |mut acc, start| {
+            if !visited[start as usize] {
+                acc.push(bfs_by(start, &mut visited, |it| self.neighbours(it)));
+            }
+
+            acc
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(g: &Graph, ids: &[NodeId]) -> Vec<String> {
+        ids.iter().map(|&it| g.label(it).to_string()).collect()
+    }
+
+    #[test]
+    fn test_interning() {
+        let mut g = Graph::new();
+        g.add_labelled_edge("a", "b").expect("short label");
+        g.add_labelled_edge("a", "c").expect("short label");
+        g.add_labelled_edge("c", "a").expect("short label");
+
+        assert_eq!(g.len(), 3);
+
+        let a = g.id("a").expect("interned");
+        let c = g.id("c").expect("interned");
+        assert_eq!(labels(&g, g.successors(a)), ["b", "c"]);
+        assert_eq!(labels(&g, g.predecessors(a)), ["c"]);
+        assert_eq!(g.id("d"), None);
+
+        let long = "a".repeat(Label::CAPACITY + 1);
+        assert_eq!(g.add_node(&long), Err(TooLong(Label::CAPACITY + 1)));
+        assert_eq!(
+            g.add_labelled_edge("a", &long),
+            Err(TooLong(Label::CAPACITY + 1))
+        );
+        assert_eq!(g.len(), 3);
+
+        g.remove_edge(a, c);
+        assert_eq!(labels(&g, g.successors(a)), ["b"]);
+        assert!(g.predecessors(c).is_empty());
+        assert_eq!(labels(&g, g.successors(c)), ["a"]);
+    }
+
+    #[test]
+    fn test_traversal() {
+        let mut g = Graph::new();
+        g.add_labelled_edge("a", "b").expect("short label");
+        g.add_labelled_edge("b", "c").expect("short label");
+        g.add_labelled_edge("d", "c").expect("short label");
+        g.add_labelled_edge("e", "f").expect("short label");
+
+        let b = g.id("b").expect("interned");
+        assert_eq!(labels(&g, &g.bfs(b)), ["b", "c"]);
+
+        let comps = g
+            .components()
+            .iter()
+            .map(|it| labels(&g, it))
+            .collect::<Vec<_>>();
+        assert_eq!(comps, [vec!["a", "b", "c", "d"], vec!["e", "f"]]);
+    }
+}
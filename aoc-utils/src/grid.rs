@@ -0,0 +1,104 @@
+use crate::parse::{Error, Source};
+
+// one row per line
+#[must_use]
+pub fn chars(inp: &str) -> Vec<Vec<char>> {
+    inp.lines().map(|it| it.chars().collect()).collect()
+}
+
+// `chars` for puzzle input: at least one cell, every row as wide as the first, and
+// nothing but the `allowed` characters
+pub fn parse(inp: &str, allowed: &str) -> Result<Vec<Vec<char>>, Error> {
+    parse_part(&Source::new(inp), inp, allowed)
+}
+
+// `parse` for one of several grids in the input, so errors point into the whole of it
+pub fn parse_part(source: &Source<'_>, part: &str, allowed: &str) -> Result<Vec<Vec<char>>, Error> {
+    let Some(width) = part
+        .lines()
+        .next()
+        .map(|it| it.chars().count())
+        .filter(|&it| it > 0)
+    else {
+        return Err(source.error(part, "empty grid"));
+    };
+
+    for line in part.lines() {
+        if let Some((idx, c)) = line.char_indices().find(|&(_, c)| !allowed.contains(c)) {
+            return Err(source.error(&line[idx..], format!("unexpected `{c}`")));
+        }
+        let found = line.chars().count();
+        if found != width {
+            return Err(source.error(line, format!("expected {width} columns, found {found}")));
+        }
+    }
+
+    Ok(chars(part))
+}
+
+// (row, column) of the first cell equal to `value`, scanning row by row
+pub fn find<T: PartialEq>(grid: &[Vec<T>], value: &T) -> Option<(usize, usize)> {
+    grid.iter().enumerate().find_map(|(row, cells)| {
+        cells
+            .iter()
+            .position(|it| it == value)
+            .map(|col| (row, col))
+    })
+}
+
+// up, right, down and left of (row, column), as long as they lie within the grid
+pub fn neighbours(
+    (row, col): (usize, usize),
+    (height, width): (usize, usize),
+) -> impl Iterator<Item = (usize, usize)> {
+    [
+        (row.wrapping_sub(1), col),
+        (row, col + 1),
+        (row + 1, col),
+        (row, col.wrapping_sub(1)),
+    ]
+    .into_iter()
+    .filter(move |&(r, c)| r < height && c < width)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find() {
+        let grid = chars("..#\n.S.\n#..");
+        assert_eq!(grid.len(), 3);
+        assert_eq!(find(&grid, &'S'), Some((1, 1)));
+        assert_eq!(find(&grid, &'#'), Some((0, 2)));
+        assert_eq!(find(&grid, &'x'), None);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("#.\n.#", ".#"), Ok(chars("#.\n.#")));
+
+        let err = |inp| parse(inp, ".#").map_err(|e| e.to_string());
+        assert_eq!(err(""), Err("line 1, column 1: empty grid".into()));
+        assert_eq!(
+            err("#.\n.x"),
+            Err("line 2, column 2: unexpected `x`".into())
+        );
+        assert_eq!(
+            err("#.\n.#."),
+            Err("line 2, column 1: expected 2 columns, found 3".into())
+        );
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let all = neighbours((1, 1), (3, 3)).collect::<Vec<_>>();
+        assert_eq!(all, [(0, 1), (1, 2), (2, 1), (1, 0)]);
+
+        let corner = neighbours((0, 0), (3, 3)).collect::<Vec<_>>();
+        assert_eq!(corner, [(0, 1), (1, 0)]);
+
+        let corner = neighbours((2, 2), (3, 3)).collect::<Vec<_>>();
+        assert_eq!(corner, [(1, 2), (2, 1)]);
+    }
+}
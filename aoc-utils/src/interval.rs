@@ -0,0 +1,284 @@
+use crate::checked::{self, Overflow};
+use itertools::Itertools;
+
+// half-open: [start, end)
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl Interval {
+    #[must_use]
+    pub const fn new(start: i64, end: i64) -> Self {
+        Self { start, end }
+    }
+
+    #[must_use]
+    pub const fn inclusive(first: i64, last: i64) -> Self {
+        Self::new(first, last + 1)
+    }
+
+    #[must_use]
+    pub const fn with_len(start: i64, len: i64) -> Self {
+        Self::new(start, start + len)
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> u64 {
+        if self.is_empty() {
+            0
+        } else {
+            self.end.abs_diff(self.start)
+        }
+    }
+
+    #[must_use]
+    pub const fn contains(&self, value: i64) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let res = Self::new(self.start.max(other.start), self.end.min(other.end));
+        (!res.is_empty()).then_some(res)
+    }
+
+    // (everything below `point`, everything from `point` on)
+    #[must_use]
+    pub fn split_at(&self, point: i64) -> (Self, Self) {
+        let point = point.clamp(self.start, self.end.max(self.start));
+        (Self::new(self.start, point), Self::new(point, self.end))
+    }
+
+    #[must_use]
+    pub const fn shift(&self, offset: i64) -> Self {
+        Self::new(self.start + offset, self.end + offset)
+    }
+}
+
+// sorted, non-overlapping and non-adjacent intervals
+#[derive(Clone, PartialEq, Eq, Hash, Default, Debug)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl IntervalSet {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { intervals: vec![] }
+    }
+
+    #[must_use]
+    pub fn intervals(&self) -> &[Interval] {
+        &self.intervals
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> u64 {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    #[must_use]
+    pub fn min(&self) -> Option<i64> {
+        self.intervals.first().map(|it| it.start)
+    }
+
+    #[must_use]
+    pub fn contains(&self, value: i64) -> bool {
+        self.intervals.iter().any(|it| it.contains(value))
+    }
+
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        self.intervals
+            .iter()
+            .chain(other.intervals.iter())
+            .copied()
+            .collect()
+    }
+
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = vec![];
+
+        let (mut lhs, mut rhs) = (0, 0);
+        while lhs < self.intervals.len() && rhs < other.intervals.len() {
+            let (l, r) = (self.intervals[lhs], other.intervals[rhs]);
+            intervals.extend(l.intersection(&r));
+
+            if l.end < r.end {
+                lhs += 1;
+            } else {
+                rhs += 1;
+            }
+        }
+
+        Self { intervals }
+    }
+
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut intervals = vec![];
+
+        for &interval in &self.intervals {
+            let mut rest = interval;
+            for cut in other
+                .intervals
+                .iter()
+                .filter(|it| it.intersection(&interval).is_some())
+            {
+                let (below, _) = rest.split_at(cut.start);
+                intervals.push(below);
+                rest = rest.split_at(cut.end).1;
+            }
+
+            intervals.push(rest);
+        }
+
+        intervals.into_iter().collect()
+    }
+
+    #[must_use]
+    pub fn split_at(&self, point: i64) -> (Self, Self) {
+        let (below, above): (Vec<_>, Vec<_>) =
+            self.intervals.iter().map(|it| it.split_at(point)).unzip();
+
+        (below.into_iter().collect(), above.into_iter().collect())
+    }
+
+    #[must_use]
+    pub fn shift(&self, offset: i64) -> Self {
+        Self {
+            intervals: self.intervals.iter().map(|it| it.shift(offset)).collect(),
+        }
+    }
+}
+
+impl From<Interval> for IntervalSet {
+    fn from(interval: Interval) -> Self {
+        std::iter::once(interval).collect()
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(iter: I) -> Self {
+        let intervals = iter
+            .into_iter()
+            .filter(|it| !it.is_empty())
+            .sorted_by_key(|it| it.start)
+            .coalesce(|l, r| {
+                if r.start <= l.end {
+                    Ok(Interval::new(l.start, l.end.max(r.end)))
+                } else {
+                    Err((l, r))
+                }
+            })
+            .collect();
+
+        Self { intervals }
+    }
+}
+
+// axis-aligned box, one interval per dimension
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct IntervalBox<const N: usize> {
+    pub dims: [Interval; N],
+}
+
+impl<const N: usize> IntervalBox<N> {
+    #[must_use]
+    pub const fn new(dims: [Interval; N]) -> Self {
+        Self { dims }
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.dims.iter().any(Interval::is_empty)
+    }
+
+    pub fn volume(&self) -> Result<u64, Overflow> {
+        checked::product(self.dims.iter().map(Interval::len))
+    }
+
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let mut dims = self.dims;
+        for (dim, o) in dims.iter_mut().zip(other.dims.iter()) {
+            *dim = dim.intersection(o)?;
+        }
+
+        Some(Self { dims })
+    }
+
+    // splits along dimension `dim`, see `Interval::split_at`
+    #[must_use]
+    pub fn split_at(&self, dim: usize, point: i64) -> (Self, Self) {
+        let (below, above) = self.dims[dim].split_at(point);
+
+        let mut lower = *self;
+        lower.dims[dim] = below;
+
+        let mut upper = *self;
+        upper.dims[dim] = above;
+
+        (lower, upper)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(intervals: &[(i64, i64)]) -> IntervalSet {
+        intervals
+            .iter()
+            .map(|&(start, end)| Interval::new(start, end))
+            .collect()
+    }
+
+    #[test]
+    fn test_interval_set() {
+        let a = set(&[(0, 5), (3, 8), (10, 12), (12, 14), (20, 20)]);
+        assert_eq!(a, set(&[(0, 8), (10, 14)]));
+        assert_eq!(a.len(), 12);
+
+        let b = set(&[(4, 11), (13, 30)]);
+        assert_eq!(a.union(&b), set(&[(0, 30)]));
+        assert_eq!(a.intersection(&b), set(&[(4, 8), (10, 11), (13, 14)]));
+        assert_eq!(a.difference(&b), set(&[(0, 4), (11, 13)]));
+        assert_eq!(b.difference(&a), set(&[(8, 10), (14, 30)]));
+
+        let (below, above) = a.split_at(6);
+        assert_eq!(below, set(&[(0, 6)]));
+        assert_eq!(above, set(&[(6, 8), (10, 14)]));
+        assert_eq!(a.shift(-2), set(&[(-2, 6), (8, 12)]));
+    }
+
+    #[test]
+    fn test_interval_box() {
+        let b = IntervalBox::new([Interval::inclusive(1, 4), Interval::inclusive(1, 10)]);
+        assert_eq!(b.volume(), Ok(40));
+
+        let (lower, upper) = b.split_at(1, 4);
+        assert_eq!(lower.volume(), Ok(12));
+        assert_eq!(upper.volume(), Ok(28));
+
+        let (lower, upper) = b.split_at(0, 100);
+        assert_eq!(lower, b);
+        assert!(upper.is_empty());
+        assert_eq!(upper.volume(), Ok(0));
+
+        assert_eq!(lower.intersection(&upper), None);
+    }
+}
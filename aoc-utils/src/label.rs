@@ -0,0 +1,125 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+const CAPACITY: usize = 15;
+
+// A short name kept inline, like `AAA`, `rn` or `broadcaster`, so parsed models don't
+// allocate per token. Compares and orders like the string it holds.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "String", into = "String")
+)]
+pub struct Label {
+    // zero padded, which keeps the derived ordering lexicographic
+    bytes: [u8; CAPACITY],
+    len: u8,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct TooLong(pub usize);
+
+impl fmt::Display for TooLong {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "label of {} bytes, at most {CAPACITY} fit", self.0)
+    }
+}
+
+impl Error for TooLong {}
+
+impl Label {
+    pub const CAPACITY: usize = CAPACITY;
+
+    pub fn new(s: &str) -> Result<Self, TooLong> {
+        if s.len() > CAPACITY {
+            return Err(TooLong(s.len()));
+        }
+
+        let mut bytes = [0; CAPACITY];
+        bytes[..s.len()].copy_from_slice(s.as_bytes());
+
+        Ok(Self {
+            bytes,
+            len: s.len() as u8,
+        })
+    }
+
+    // only ever holds bytes copied from a `str`, so this can't fail
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.bytes[..self.len as usize]).expect("copied from a str")
+    }
+}
+
+impl FromStr for Label {
+    type Err = TooLong;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl TryFrom<String> for Label {
+    type Error = TooLong;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::new(&s)
+    }
+}
+
+impl From<Label> for String {
+    fn from(label: Label) -> Self {
+        label.as_str().to_string()
+    }
+}
+
+impl PartialEq<str> for Label {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Label {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl fmt::Display for Label {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Debug for Label {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let label = Label::new("broadcaster").expect("fits");
+        assert_eq!(label, "broadcaster");
+        assert_eq!(label.to_string(), "broadcaster");
+        assert_eq!(format!("{label:?}"), "\"broadcaster\"");
+        assert_eq!(Label::new(""), "".parse());
+
+        assert_eq!(std::mem::size_of::<Label>(), 16);
+        assert_eq!("a".repeat(16).parse::<Label>(), Err(TooLong(16)));
+    }
+
+    #[test]
+    fn test_ordering() {
+        let mut labels = ["zz", "a", "ab", "b", ""].map(|it| Label::new(it).expect("fits"));
+        labels.sort();
+        assert_eq!(labels.map(|it| it.to_string()), ["", "a", "ab", "b", "zz"]);
+    }
+}
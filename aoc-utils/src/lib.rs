@@ -0,0 +1,22 @@
+#![deny(unsafe_code)]
+#![warn(nonstandard_style, rust_2018_idioms)]
+#![allow(
+    clippy::implicit_hasher,
+    clippy::missing_errors_doc,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+
+// Year-independent helpers, shared by the solutions of every year.
+
+pub mod cancel;
+pub mod checked;
+pub mod cycles;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod interval;
+pub mod label;
+pub mod parse;
+pub mod progress;
+pub mod stats;
@@ -0,0 +1,259 @@
+use std::fmt;
+use std::str::FromStr;
+
+// whitespace separated numbers
+pub fn numbers<T: FromStr>(inp: &str) -> Result<Vec<T>, T::Err> {
+    inp.split_ascii_whitespace().map(str::parse).collect()
+}
+
+// 1-based, the way editors count
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct Pos {
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Error {
+    pub pos: Pos,
+    pub message: String,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.pos.line, self.pos.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for Error {}
+
+// The whole puzzle input. Every helper takes and returns slices of it, which is all it
+// needs to tell where an error is.
+#[derive(Copy, Clone, Debug)]
+pub struct Source<'a> {
+    text: &'a str,
+}
+
+impl<'a> Source<'a> {
+    #[must_use]
+    pub const fn new(text: &'a str) -> Self {
+        Self { text }
+    }
+
+    #[must_use]
+    pub const fn text(&self) -> &'a str {
+        self.text
+    }
+
+    // panics unless `part` is a slice of the source
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn pos(&self, part: &str) -> Pos {
+        let offset = (part.as_ptr() as usize)
+            .checked_sub(self.text.as_ptr() as usize)
+            .filter(|&it| it <= self.text.len())
+            .expect("slice of the source");
+
+        let before = &self.text[..offset];
+        let line_start = before.rfind('\n').map_or(0, |it| it + 1);
+
+        Pos {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+
+    #[must_use]
+    pub fn error(&self, at: &str, message: impl fmt::Display) -> Error {
+        Error {
+            pos: self.pos(at),
+            message: message.to_string(),
+        }
+    }
+
+    pub fn split_once(&self, part: &'a str, delimiter: &str) -> Result<(&'a str, &'a str), Error> {
+        part.split_once(delimiter)
+            .ok_or_else(|| self.error(part, format!("expected `{delimiter}`")))
+    }
+
+    // any `FromStr` value, failing at `part`
+    pub fn parse<T>(&self, part: &str) -> Result<T, Error>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        part.parse()
+            .map_err(|e| self.error(part, format!("`{part}`: {e}")))
+    }
+
+    // every integer in `part`; a `-` right in front of the digits makes it negative
+    pub fn integers<T>(&self, part: &'a str) -> Result<Vec<T>, Error>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        integer_tokens(part).map(|it| self.parse(it)).collect()
+    }
+
+    pub fn integers_at<T>(&self, part: &'a str) -> Result<Vec<(Pos, T)>, Error>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        integer_tokens(part)
+            .map(|it| Ok((self.pos(it), self.parse(it)?)))
+            .collect()
+    }
+
+    // exactly `N` integers, e.g. the six coordinates of `1,0,1~1,2,1`
+    pub fn integer_array<T, const N: usize>(&self, part: &'a str) -> Result<[T; N], Error>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let values = self.integers(part)?;
+        let found = values.len();
+
+        values
+            .try_into()
+            .map_err(|_| self.error(part, format!("expected {N} numbers, found {found}")))
+    }
+
+    // `key: value`, both trimmed; the key ends at the first colon
+    pub fn key_value(&self, part: &'a str) -> Result<(&'a str, &'a str), Error> {
+        let (key, value) = self.split_once(part, ":")?;
+        Ok((key.trim(), value.trim()))
+    }
+
+    // a `key: value` per non-empty line
+    pub fn key_values(&self, block: &'a str) -> Result<Vec<(&'a str, &'a str)>, Error> {
+        block
+            .lines()
+            .filter(|it| !it.trim().is_empty())
+            .map(|it| self.key_value(it))
+            .collect()
+    }
+
+    pub fn field(&self, block: &'a str, key: &str) -> Result<&'a str, Error> {
+        self.key_values(block)?
+            .into_iter()
+            .find(|&(it, _)| it == key)
+            .map(|(_, value)| value)
+            .ok_or_else(|| self.error(block, format!("missing `{key}:`")))
+    }
+
+    // Blocks separated by blank lines, each opening with `label:`. The body is the rest of
+    // the block, so it may span several lines.
+    pub fn sections(&self) -> impl Iterator<Item = Result<(&'a str, &'a str), Error>> + '_ {
+        self.text
+            .split("\n\n")
+            .filter(|it| !it.trim().is_empty())
+            .map(|it| self.key_value(it))
+    }
+}
+
+fn integer_tokens(part: &str) -> impl Iterator<Item = &str> {
+    let bytes = part.as_bytes();
+    let mut idx = 0;
+
+    std::iter::from_fn(move || {
+        while idx < bytes.len() && !bytes[idx].is_ascii_digit() {
+            idx += 1;
+        }
+        if idx == bytes.len() {
+            return None;
+        }
+
+        let start = if idx > 0 && bytes[idx - 1] == b'-' {
+            idx - 1
+        } else {
+            idx
+        };
+        while idx < bytes.len() && bytes[idx].is_ascii_digit() {
+            idx += 1;
+        }
+
+        Some(&part[start..idx])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(numbers::<i64>(" 1  -2\t3 "), Ok(vec![1, -2, 3]));
+        assert_eq!(numbers::<u8>(""), Ok(vec![]));
+        assert!(numbers::<u8>("1 x").is_err());
+        assert!(numbers::<u8>("256").is_err());
+    }
+
+    #[test]
+    fn test_integers_with_positions() {
+        let source = Source::new("x: 1, -23\nä 4~-5 seed-to-soil 300");
+        let pos = |line, column| Pos { line, column };
+
+        assert_eq!(
+            source.integers_at::<i64>(source.text()),
+            Ok(vec![
+                (pos(1, 4), 1),
+                (pos(1, 7), -23),
+                (pos(2, 3), 4),
+                (pos(2, 5), -5),
+                (pos(2, 21), 300)
+            ])
+        );
+        assert_eq!(
+            source.integer_array::<i64, 3>(&source.text()[10..]),
+            Ok([4, -5, 300])
+        );
+
+        let err = source
+            .integers::<u8>(source.text())
+            .expect_err("out of range");
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 7: `-23`: invalid digit found in string"
+        );
+
+        let err = source
+            .integer_array::<i64, 2>(source.text())
+            .expect_err("five numbers");
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 1: expected 2 numbers, found 5"
+        );
+    }
+
+    #[test]
+    fn test_sections_and_fields() {
+        let source =
+            Source::new("seeds: 79 14\n\nsoil map:\n50 98 2\n52 50 48\n\nTime: 7\nSpeed 9");
+
+        let sections = source.sections().collect::<Result<Vec<_>, _>>();
+        assert_eq!(
+            sections,
+            Ok(vec![
+                ("seeds", "79 14"),
+                ("soil map", "50 98 2\n52 50 48"),
+                ("Time", "7\nSpeed 9")
+            ])
+        );
+
+        let block = &source.text()[source.text().find("Time").expect("block")..];
+        assert_eq!(
+            source.key_values(block).expect_err("no colon").to_string(),
+            "line 8, column 1: expected `:`"
+        );
+        assert_eq!(source.field(&block[..7], "Time"), Ok("7"));
+        assert_eq!(
+            source.field(&block[..7], "Distance").map_err(|e| e.pos),
+            Err(Pos { line: 7, column: 1 })
+        );
+    }
+}
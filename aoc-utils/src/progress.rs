@@ -0,0 +1,158 @@
+use std::io::{Stderr, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+// Reported to by slow solvers as they get through their work. Solvers take it as a
+// generic parameter, so passing `&()` compiles the reporting away entirely.
+pub trait Progress {
+    // called before the first unit of work, with the amount of it if known upfront
+    fn start(&self, _total: Option<usize>) {}
+
+    fn advance(&self, _done: usize) {}
+
+    fn finish(&self) {}
+}
+
+impl Progress for () {}
+
+const WIDTH: usize = 40;
+const REDRAW: Duration = Duration::from_millis(100);
+
+// sentinel for a total that isn't known
+const UNKNOWN: usize = usize::MAX;
+
+#[must_use]
+pub fn render(done: usize, total: Option<usize>) -> String {
+    match total {
+        Some(total) if total > 0 => {
+            let filled = (done.min(total) * WIDTH) / total;
+            format!(
+                "[{}{}] {done}/{total}",
+                "#".repeat(filled),
+                ".".repeat(WIDTH - filled)
+            )
+        }
+        _ => format!("{done} done"),
+    }
+}
+
+// Draws a progress bar on stderr, or whatever it's given to write to, at most every 100ms,
+// and clears it once finished.
+#[derive(Debug)]
+pub struct Bar<W: Write = Stderr> {
+    label: String,
+    total: AtomicUsize,
+    done: AtomicUsize,
+    drawn: Mutex<Option<Instant>>,
+    out: Mutex<W>,
+}
+
+impl Bar {
+    #[must_use]
+    pub fn new(label: &str) -> Self {
+        Self::with_writer(label, std::io::stderr())
+    }
+}
+
+impl<W: Write> Bar<W> {
+    #[must_use]
+    pub fn with_writer(label: &str, out: W) -> Self {
+        Self {
+            label: label.to_string(),
+            total: AtomicUsize::new(UNKNOWN),
+            done: AtomicUsize::new(0),
+            drawn: Mutex::new(None),
+            out: Mutex::new(out),
+        }
+    }
+
+    // gives back the writer, along with everything drawn to it
+    pub fn into_inner(self) -> W {
+        self.out
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self, s: &str) {
+        let mut out = self.out.lock().unwrap_or_else(PoisonError::into_inner);
+        let _ = write!(out, "\r\x1b[2K{s}");
+        let _ = out.flush();
+    }
+
+    fn draw(&self) {
+        let total = self.total.load(Ordering::Relaxed);
+        let line = render(
+            self.done.load(Ordering::Relaxed),
+            (total != UNKNOWN).then_some(total),
+        );
+
+        self.write(&format!("{} {line}", self.label));
+    }
+}
+
+impl<W: Write> Progress for Bar<W> {
+    fn start(&self, total: Option<usize>) {
+        self.total
+            .store(total.unwrap_or(UNKNOWN), Ordering::Relaxed);
+        self.done.store(0, Ordering::Relaxed);
+        self.draw();
+    }
+
+    fn advance(&self, done: usize) {
+        self.done.fetch_add(done, Ordering::Relaxed);
+
+        // whoever can't get the lock skips drawing, someone else is on it
+        let Ok(mut drawn) = self.drawn.try_lock() else {
+            return;
+        };
+
+        if drawn.is_none_or(|it| it.elapsed() >= REDRAW) {
+            *drawn = Some(Instant::now());
+            self.draw();
+        }
+    }
+
+    fn finish(&self) {
+        self.write("");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        assert_eq!(render(0, Some(4)), format!("[{}] 0/4", ".".repeat(40)));
+        assert_eq!(
+            render(1, Some(4)),
+            format!("[{}{}] 1/4", "#".repeat(10), ".".repeat(30))
+        );
+        assert_eq!(render(5, Some(4)), format!("[{}] 5/4", "#".repeat(40)));
+        assert_eq!(render(7, None), "7 done");
+        assert_eq!(render(7, Some(0)), "7 done");
+    }
+
+    #[test]
+    fn test_bar_counts() {
+        let bar = Bar::with_writer("test", vec![]);
+        bar.start(Some(3));
+        bar.advance(1);
+        bar.advance(2);
+        assert_eq!(bar.done.load(Ordering::Relaxed), 3);
+
+        bar.start(None);
+        assert_eq!(bar.done.load(Ordering::Relaxed), 0);
+        assert_eq!(bar.total.load(Ordering::Relaxed), UNKNOWN);
+        bar.finish();
+
+        let out = String::from_utf8(bar.into_inner()).expect("utf-8");
+        let lines = out.split("\r\x1b[2K").collect::<Vec<_>>();
+        assert_eq!(lines[0], "");
+        assert_eq!(lines[1], format!("test {}", render(0, Some(3))));
+        assert_eq!(lines[2], format!("test {}", render(1, Some(3))));
+        assert_eq!(lines[lines.len() - 2], "test 0 done");
+        assert_eq!(lines[lines.len() - 1], "");
+    }
+}
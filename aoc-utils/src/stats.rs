@@ -0,0 +1,106 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Mutex;
+
+// Named counters filled in by solvers as they run, for comparing variants by more
+// than wall time. Like `Progress`, `&()` compiles the bookkeeping away.
+pub trait Stats {
+    fn add(&self, _key: &'static str, _n: usize) {}
+
+    fn set(&self, _key: &'static str, _value: usize) {}
+
+    // keeps the largest value seen
+    fn max(&self, _key: &'static str, _value: usize) {}
+}
+
+impl Stats for () {}
+
+#[derive(Default, Debug)]
+pub struct Counters(Mutex<BTreeMap<&'static str, usize>>);
+
+impl Counters {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn update(&self, key: &'static str, f: impl FnOnce(&mut usize)) {
+        let mut counters = self
+            .0
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        f(counters.entry(key).or_default());
+    }
+
+    // sorted by key
+    #[must_use]
+    pub fn to_vec(&self) -> Vec<(&'static str, usize)> {
+        let counters = self
+            .0
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        counters.iter().map(|(&key, &value)| (key, value)).collect()
+    }
+}
+
+impl Stats for Counters {
+    fn add(&self, key: &'static str, n: usize) {
+        self.update(key, |it| *it += n);
+    }
+
+    fn set(&self, key: &'static str, value: usize) {
+        self.update(key, |it| *it = value);
+    }
+
+    fn max(&self, key: &'static str, value: usize) {
+        self.update(key, |it| *it = value.max(*it));
+    }
+}
+
+// `key=value` pairs, sorted by key
+impl fmt::Display for Counters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, (key, value)) in self.to_vec().into_iter().enumerate() {
+            if idx > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{key}={value}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counters() {
+        let stats = Counters::new();
+        stats.add("pops", 2);
+        stats.add("pops", 3);
+        stats.set("cycle_length", 7);
+        stats.set("cycle_length", 9);
+        stats.max("widest", 4);
+        stats.max("widest", 1);
+
+        assert_eq!(
+            stats.to_vec(),
+            [("cycle_length", 9), ("pops", 5), ("widest", 4)]
+        );
+        assert_eq!(stats.to_string(), "cycle_length=9 pops=5 widest=4");
+    }
+
+    #[test]
+    fn test_unit_ignores_everything() {
+        fn count<S: Stats + ?Sized>(stats: &S) {
+            stats.add("calls", 1);
+        }
+
+        count(&());
+        let counters = Counters::new();
+        count(&counters as &dyn Stats);
+        assert_eq!(counters.to_vec(), [("calls", 1)]);
+    }
+}
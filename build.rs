@@ -0,0 +1,32 @@
+// Hashes the sources into `AOC_SOURCE_HASH`, which keys the cache: entries written by
+// other code are missed instead of trusted.
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+
+fn hash_dir(dir: &Path, hasher: &mut DefaultHasher) {
+    let mut paths = std::fs::read_dir(dir)
+        .expect("source dir")
+        .map(|it| it.expect("source dir entry").path())
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    for path in paths {
+        if path.is_dir() {
+            hash_dir(&path, hasher);
+        } else {
+            path.file_name().hash(hasher);
+            std::fs::read(&path).expect("source file").hash(hasher);
+        }
+    }
+}
+
+fn main() {
+    let mut hasher = DefaultHasher::new();
+    for dir in ["src", "aoc-utils/src"] {
+        println!("cargo:rerun-if-changed={dir}");
+        hash_dir(Path::new(dir), &mut hasher);
+    }
+
+    println!("cargo:rustc-env=AOC_SOURCE_HASH={:016x}", hasher.finish());
+}
@@ -0,0 +1,9 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(inp) = std::str::from_utf8(data) {
+        let _ = aoc_2023::registry::fuzz(4, inp);
+    }
+});
@@ -0,0 +1,198 @@
+use crate::registry::{self, caught, Context, Params, DEFAULT_NAME};
+use aoc_utils::cancel::Token;
+use std::collections::BTreeMap;
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Expected answers live next to their input, e.g. `alice.txt.answers`.
+pub const SIDECAR_EXTENSION: &str = "answers";
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Outcome {
+    pub file: PathBuf,
+    pub part: u32,
+    pub answer: Result<String, String>,
+    pub expected: Option<String>,
+    pub generate: Duration,
+    pub solve: Duration,
+}
+
+impl Outcome {
+    // `None` without a sidecar entry to compare against
+    #[must_use]
+    pub fn matches(&self) -> Option<bool> {
+        let expected = self.expected.as_ref()?;
+        Some(self.answer.as_ref().is_ok_and(|it| it == expected))
+    }
+}
+
+fn sidecar_path(file: &Path) -> PathBuf {
+    let mut path = file.as_os_str().to_owned();
+    path.push(".");
+    path.push(SIDECAR_EXTENSION);
+    PathBuf::from(path)
+}
+
+// One `<part> <answer>` per line, `#` starts a comment.
+pub fn parse_sidecar(inp: &str) -> Result<BTreeMap<u32, String>, String> {
+    let mut result = BTreeMap::new();
+
+    for (idx, line) in inp.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let parsed = line
+            .split_once(char::is_whitespace)
+            .and_then(|(part, answer)| Some((part.parse().ok()?, answer.trim().to_string())));
+        let Some((part, answer)) = parsed else {
+            return Err(format!("line {}: expected `<part> <answer>`", idx + 1));
+        };
+
+        result.insert(part, answer);
+    }
+
+    Ok(result)
+}
+
+// Every input in `dir`, sorted by name, leaving out the sidecars.
+pub fn input_files(dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut files = vec![];
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file() && path.extension().is_none_or(|it| it != SIDECAR_EXTENSION) {
+            files.push(path);
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+fn run_file(day: u32, file: &Path) -> Vec<Outcome> {
+    let inp = match std::fs::read_to_string(file) {
+        Ok(inp) => inp,
+        Err(e) => {
+            return vec![Outcome {
+                file: file.to_path_buf(),
+                part: 0,
+                answer: Err(e.to_string()),
+                expected: None,
+                generate: Duration::ZERO,
+                solve: Duration::ZERO,
+            }]
+        }
+    };
+
+    // cargo-aoc strips trailing newlines before handing the input to the generators
+    let inp = inp.trim_end_matches('\n');
+
+    let expected = std::fs::read_to_string(sidecar_path(file))
+        .map_or_else(|_| Ok(BTreeMap::new()), |it| parse_sidecar(&it));
+
+    registry::solvers()
+        .into_iter()
+        .filter(|it| it.day == day && it.name == DEFAULT_NAME)
+        .map(|solver| {
+            let start = Instant::now();
+            let part = caught(|| solver.prepare(inp, &Params::default()));
+            let generate = start.elapsed();
+
+            let start = Instant::now();
+            let answer = part.and_then(|part| {
+                caught(|| part(&Context::new(&Token::new())))?.map_err(|e| e.to_string())
+            });
+            let solve = start.elapsed();
+
+            // a broken sidecar fails every part of its input rather than passing silently
+            let (answer, expected) = match &expected {
+                Ok(expected) => (answer, expected.get(&solver.part).cloned()),
+                Err(e) => (Err(format!("bad sidecar: {e}")), None),
+            };
+
+            Outcome {
+                file: file.to_path_buf(),
+                part: solver.part,
+                answer,
+                expected,
+                generate,
+                solve,
+            }
+        })
+        .collect()
+}
+
+// Runs both parts of `day` on every file, spread over `jobs` threads. Results come back
+// in the order of `files`, however they were scheduled.
+#[must_use]
+pub fn run(day: u32, files: &[PathBuf], jobs: usize) -> Vec<Outcome> {
+    let next = AtomicUsize::new(0);
+
+    let mut results = thread::scope(|scope| {
+        let workers = (0..jobs.clamp(1, files.len().max(1)))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = vec![];
+                    loop {
+                        let idx = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(file) = files.get(idx) else {
+                            return done;
+                        };
+                        done.push((idx, run_file(day, file)));
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        workers
+            .into_iter()
+            .flat_map(|it| it.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+            .collect::<Vec<_>>()
+    });
+
+    results.sort_by_key(|(idx, _)| *idx);
+    results.into_iter().flat_map(|(_, it)| it).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sidecar() {
+        assert_eq!(
+            parse_sidecar("# from the puzzle page\n1 288\n\n2   71503\n"),
+            Ok(BTreeMap::from([(1, "288".into()), (2, "71503".into())]))
+        );
+        assert_eq!(
+            parse_sidecar("1 288\ntwo 71503"),
+            Err("line 2: expected `<part> <answer>`".into())
+        );
+    }
+
+    #[test]
+    fn test_run() {
+        let dir = std::env::temp_dir().join(format!("aoc-batch-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("temp dir");
+
+        let inp = include_str!("../examples/2023/day06/example.txt");
+        std::fs::write(dir.join("a.txt"), inp).expect("write input");
+        std::fs::write(dir.join("a.txt.answers"), "1 288\n2 1\n").expect("write sidecar");
+        std::fs::write(dir.join("b.txt"), inp).expect("write input");
+
+        let files = input_files(&dir).expect("list inputs");
+        let outcomes = run(6, &files, 4);
+        std::fs::remove_dir_all(&dir).expect("clean up");
+
+        assert_eq!(files, [dir.join("a.txt"), dir.join("b.txt")]);
+        assert_eq!(
+            outcomes.iter().map(Outcome::matches).collect::<Vec<_>>(),
+            [Some(true), Some(false), None, None]
+        );
+        assert_eq!(outcomes[3].answer, Ok("71503".into()));
+    }
+}
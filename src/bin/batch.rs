@@ -0,0 +1,101 @@
+use aoc_2023::batch::{self, Outcome};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: batch [--jobs N] <day> <input dir>";
+
+fn status(outcome: &Outcome) -> &'static str {
+    match (&outcome.answer, outcome.matches()) {
+        (Err(_), _) => "ERROR",
+        (Ok(_), Some(true)) => "ok",
+        (Ok(_), Some(false)) => "WRONG",
+        (Ok(_), None) => "-",
+    }
+}
+
+fn print_row(outcome: &Outcome) {
+    let file = outcome.file.file_name().map_or_else(
+        || outcome.file.display().to_string(),
+        |it| it.to_string_lossy().into(),
+    );
+
+    let answer = match (&outcome.answer, &outcome.expected) {
+        (Ok(answer), Some(expected)) if answer != expected => {
+            format!("{answer} (expected {expected})")
+        }
+        (Ok(answer), _) => answer.clone(),
+        (Err(e), _) => format!("error: {e}"),
+    };
+
+    println!(
+        "{file:<24} {:<5} {:<6} {:>12.3?} {:>12.3?}  {answer}",
+        outcome.part,
+        status(outcome),
+        outcome.generate,
+        outcome.solve
+    );
+}
+
+fn main() -> ExitCode {
+    #[cfg(feature = "trace")]
+    let _trace = match aoc_2023::trace::init() {
+        Ok(guard) => guard,
+        Err(e) => {
+            eprintln!("failed to set up tracing: {e}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut jobs = 1;
+    let mut positional = vec![];
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--jobs" {
+            let Some(n) = args.next().and_then(|it| it.parse().ok()) else {
+                eprintln!("{USAGE}");
+                return ExitCode::from(2);
+            };
+            jobs = n;
+        } else {
+            positional.push(arg);
+        }
+    }
+
+    let [day, dir] = positional.as_slice() else {
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    };
+
+    let Ok(day) = day.parse::<u32>() else {
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    };
+
+    let files = match batch::input_files(&PathBuf::from(dir)) {
+        Ok(files) => files,
+        Err(e) => {
+            eprintln!("failed to read {dir}: {e}");
+            return ExitCode::from(2);
+        }
+    };
+
+    println!(
+        "{:<24} {:<5} {:<6} {:>12} {:>12}  answer",
+        "input", "part", "status", "generate", "solve"
+    );
+
+    let outcomes = batch::run(day, &files, jobs);
+    for outcome in &outcomes {
+        print_row(outcome);
+    }
+
+    if outcomes
+        .iter()
+        .any(|it| it.answer.is_err() || it.matches() == Some(false))
+    {
+        return ExitCode::FAILURE;
+    }
+
+    ExitCode::SUCCESS
+}
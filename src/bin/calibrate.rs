@@ -0,0 +1,245 @@
+use aoc_2023::calibration::{
+    self, Combine, DigitVocabulary, Number, NumberPhrases, Token, TokenKind,
+};
+use std::fs::File;
+use std::io::BufReader;
+use std::ops::Range;
+use std::path::Path;
+use std::process::ExitCode;
+
+const USAGE: &str =
+    "usage: calibrate [--words | --phrases [--combine numbers|digits]] [--highlight] [--jobs N] [input file]";
+
+const FIRST: &str = "\x1b[1;32m";
+const LAST: &str = "\x1b[1;36m";
+const BOTH: &str = "\x1b[1;33m";
+const RESET: &str = "\x1b[0m";
+
+enum Mode {
+    Digits(DigitVocabulary),
+    Phrases(NumberPhrases, Combine),
+}
+
+// what a line's value was made of
+struct Found {
+    value: u64,
+    first: (Range<usize>, String),
+    last: (Range<usize>, String),
+}
+
+// first match in green, last in cyan, and where they overlap in yellow
+fn highlight(line: &str, first: &Range<usize>, last: &Range<usize>) -> String {
+    let style = |idx: usize| match (first.contains(&idx), last.contains(&idx)) {
+        (true, true) => Some(BOTH),
+        (true, false) => Some(FIRST),
+        (false, true) => Some(LAST),
+        (false, false) => None,
+    };
+
+    let mut result = String::new();
+    let mut current = None;
+    for (idx, c) in line.char_indices() {
+        let next = style(idx);
+        if next != current {
+            if current.is_some() {
+                result.push_str(RESET);
+            }
+            if let Some(next) = next {
+                result.push_str(next);
+            }
+            current = next;
+        }
+        result.push(c);
+    }
+    if current.is_some() {
+        result.push_str(RESET);
+    }
+
+    result
+}
+
+fn describe_token(line: &str, token: &Token) -> (Range<usize>, String) {
+    let kind = match token.kind {
+        TokenKind::Digit => "digit",
+        TokenKind::Word => "word",
+    };
+
+    (
+        token.span.clone(),
+        format!(
+            "{} `{}` at {}..{} ({kind})",
+            token.value,
+            &line[token.span.clone()],
+            token.span.start,
+            token.span.end
+        ),
+    )
+}
+
+fn describe_number(line: &str, number: &Number) -> (Range<usize>, String) {
+    (
+        number.span.clone(),
+        format!(
+            "{} `{}` at {}..{}",
+            number.value,
+            &line[number.span.clone()],
+            number.span.start,
+            number.span.end
+        ),
+    )
+}
+
+fn find(line: &str, mode: &Mode) -> Result<Option<Found>, String> {
+    match mode {
+        Mode::Digits(vocabulary) => Ok(calibration::explain(line, vocabulary).map(|it| Found {
+            value: u64::from(it.value),
+            first: describe_token(line, &it.first),
+            last: describe_token(line, &it.last),
+        })),
+        Mode::Phrases(phrases, combine) => {
+            let numbers = calibration::numbers(line, phrases).map_err(|e| e.to_string())?;
+            let (Some(first), Some(last)) = (numbers.first(), numbers.last()) else {
+                return Ok(None);
+            };
+
+            Ok(Some(Found {
+                value: combine.apply(first, last).map_err(|e| e.to_string())?,
+                first: describe_number(line, first),
+                last: describe_number(line, last),
+            }))
+        }
+    }
+}
+
+fn stream(path: &str, vocabulary: &DigitVocabulary, jobs: usize) -> ExitCode {
+    let report = |number| eprintln!("line {number}: no digit");
+    let totals = if jobs > 1 {
+        calibration::calibrate_file_parallel(Path::new(path), vocabulary, jobs, report)
+    } else {
+        File::open(path)
+            .and_then(|it| calibration::calibrate_reader(BufReader::new(it), vocabulary, report))
+    };
+
+    match totals {
+        Ok(totals) => {
+            println!("total: {}", totals.total);
+            if totals.missing > 0 {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            }
+        }
+        Err(e) => {
+            eprintln!("failed to calibrate {path}: {e}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let mut words = false;
+    let mut combine = None;
+    let mut highlighting = false;
+    let mut jobs = 1;
+    let mut path = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--words" => words = true,
+            "--phrases" => combine = Some(combine.unwrap_or_default()),
+            "--combine" => {
+                combine = match args.next().as_deref() {
+                    Some("numbers") => Some(Combine::Numbers),
+                    Some("digits") => Some(Combine::Digits),
+                    _ => {
+                        eprintln!("{USAGE}");
+                        return ExitCode::from(2);
+                    }
+                }
+            }
+            "--highlight" => highlighting = true,
+            "--jobs" => {
+                let Some(n) = args.next().and_then(|it| it.parse().ok()) else {
+                    eprintln!("{USAGE}");
+                    return ExitCode::from(2);
+                };
+                jobs = n;
+            }
+            _ if !arg.starts_with("--") && path.is_none() => path = Some(arg),
+            _ => {
+                eprintln!("{USAGE}");
+                return ExitCode::from(2);
+            }
+        }
+    }
+
+    let mode = match (words, combine) {
+        (true, Some(_)) => {
+            eprintln!("{USAGE}");
+            return ExitCode::from(2);
+        }
+        (false, Some(combine)) => Mode::Phrases(NumberPhrases::english(), combine),
+        (true, None) => Mode::Digits(DigitVocabulary::english()),
+        (false, None) => Mode::Digits(DigitVocabulary::digits()),
+    };
+
+    let path = path.unwrap_or_else(|| "input/2023/day1.txt".to_string());
+
+    // digits alone never need the whole file at once
+    if let (Mode::Digits(vocabulary), false) = (&mode, highlighting) {
+        return stream(&path, vocabulary, jobs);
+    }
+
+    let inp = match std::fs::read_to_string(&path) {
+        Ok(inp) => inp,
+        Err(e) => {
+            eprintln!("failed to read {path}: {e}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut total = 0u64;
+    let mut result = ExitCode::SUCCESS;
+    for (line, number) in inp.lines().zip(1..) {
+        let found = match find(line, &mode) {
+            Ok(Some(found)) => found,
+            Ok(None) => {
+                if highlighting {
+                    println!("{number:>5}: {line}");
+                    println!("       no digit");
+                } else {
+                    eprintln!("line {number}: no digit");
+                }
+                result = ExitCode::FAILURE;
+                continue;
+            }
+            Err(e) => {
+                eprintln!("line {number}: {e}");
+                result = ExitCode::FAILURE;
+                continue;
+            }
+        };
+
+        let Some(sum) = total.checked_add(found.value) else {
+            eprintln!("line {number}: the total overflows");
+            return ExitCode::FAILURE;
+        };
+        total = sum;
+
+        if highlighting {
+            println!(
+                "{number:>5}: {}",
+                highlight(line, &found.first.0, &found.last.0)
+            );
+            println!(
+                "       first {}, last {} => {}",
+                found.first.1, found.last.1, found.value
+            );
+        }
+    }
+
+    println!("total: {total}");
+
+    result
+}
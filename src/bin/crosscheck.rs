@@ -0,0 +1,77 @@
+use aoc_2023::registry;
+use aoc_utils::progress::Bar;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    #[cfg(feature = "trace")]
+    let _trace = match aoc_2023::trace::init() {
+        Ok(guard) => guard,
+        Err(e) => {
+            eprintln!("failed to set up tracing: {e}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let (flags, args): (Vec<_>, Vec<_>) = std::env::args()
+        .skip(1)
+        .partition(|it| it.starts_with("--"));
+
+    let Some(day) = args.first().and_then(|it| it.parse::<u32>().ok()) else {
+        eprintln!("usage: crosscheck [--progress] [--stats] <day> [input file]");
+        return ExitCode::from(2);
+    };
+
+    let path = args
+        .get(1)
+        .cloned()
+        .unwrap_or_else(|| format!("input/2023/day{day}.txt"));
+
+    let inp = match std::fs::read_to_string(&path) {
+        Ok(inp) => inp,
+        Err(e) => {
+            eprintln!("failed to read {path}: {e}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let collect_stats = flags.iter().any(|it| it == "--stats");
+    let checks = if flags.iter().any(|it| it == "--progress") {
+        let bar = Bar::new(&format!("day{day:02}"));
+        registry::crosscheck_with(day, &inp, Some(&bar), collect_stats)
+    } else {
+        registry::crosscheck_with(day, &inp, None, collect_stats)
+    };
+
+    if checks.is_empty() {
+        eprintln!("no solutions registered for day {day}");
+        return ExitCode::from(2);
+    }
+
+    let mut result = ExitCode::SUCCESS;
+    for check in &checks {
+        let status = if check.agrees() { "ok" } else { "MISMATCH" };
+        println!("day{:02} part{}: {status}", check.day, check.part);
+
+        for (name, answer) in &check.answers {
+            match answer {
+                Ok(answer) => println!("  {name:<16} {answer}"),
+                Err(e) => println!("  {name:<16} error: {e}"),
+            }
+        }
+
+        for (name, counters) in check.stats.iter().filter(|(_, it)| !it.is_empty()) {
+            let counters = counters
+                .iter()
+                .map(|(key, value)| format!("{key}={value}"))
+                .collect::<Vec<_>>()
+                .join(" ");
+            println!("  {name:<16} stats: {counters}");
+        }
+
+        if !check.agrees() {
+            result = ExitCode::FAILURE;
+        }
+    }
+
+    result
+}
@@ -0,0 +1,216 @@
+use aoc_2023::explore::{self, Simulation};
+use aoc_2023::registry::{self, Context, DEFAULT_NAME};
+use aoc_utils::cancel::Token;
+use aoc_utils::parse::Error;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::widgets::{Block, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::process::ExitCode;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::Duration;
+
+// panics on these threads end up as the part's answer instead of on the screen
+const SOLVER_THREAD: &str = "solver";
+
+const HELP: &str = "[space] step  [r] run/pause  [arrows] scroll  [q] quit";
+
+struct App {
+    day: u32,
+    // the raw input, for days without a simulation
+    input: Vec<String>,
+    sim: Option<Box<dyn Simulation>>,
+    running: bool,
+    answers: Vec<(u32, Option<String>)>,
+    finished: Receiver<(u32, String)>,
+    // stops the parts still running once the explorer quits
+    solving: Token,
+    scroll: (u16, u16),
+}
+
+impl App {
+    fn new(day: u32, inp: &str) -> Result<Self, Error> {
+        let sim = explore::simulation(day, inp)?;
+        let (tx, finished) = mpsc::channel();
+        let solving = Token::new();
+
+        // every part gets its own thread, so a slow part 2 doesn't hold back part 1
+        let mut answers = vec![];
+        for solver in registry::solvers() {
+            if solver.day != day || solver.name != DEFAULT_NAME {
+                continue;
+            }
+
+            answers.push((solver.part, None));
+
+            let tx = tx.clone();
+            let inp = inp.trim_end_matches('\n').to_string();
+            let token = solving.clone();
+            let name = format!("{SOLVER_THREAD} part{}", solver.part);
+            let spawned = thread::Builder::new().name(name).spawn(move || {
+                let answer = panic::catch_unwind(AssertUnwindSafe(|| {
+                    solver.solve_with(&inp, &registry::Params::default(), &Context::new(&token))
+                }))
+                .unwrap_or_else(|e| Err(registry::panic_message(&*e).into()))
+                .unwrap_or_else(|e| format!("error: {e}"));
+                let _ = tx.send((solver.part, answer));
+            });
+            spawned.expect("solver thread");
+        }
+
+        Ok(Self {
+            day,
+            input: inp.lines().map(String::from).collect(),
+            sim,
+            running: false,
+            answers,
+            finished,
+            solving,
+            scroll: (0, 0),
+        })
+    }
+
+    fn step(&mut self) {
+        if let Some(sim) = &mut self.sim {
+            self.running &= sim.step();
+        }
+    }
+
+    fn tick(&mut self) {
+        while let Ok((part, answer)) = self.finished.try_recv() {
+            if let Some((_, it)) = self.answers.iter_mut().find(|(p, _)| *p == part) {
+                *it = Some(answer);
+            }
+        }
+
+        if self.running {
+            self.step();
+        }
+    }
+
+    fn status(&self) -> String {
+        let sim = self.sim.as_ref().map_or_else(String::new, |sim| {
+            format!("step {}, {}", sim.steps(), sim.status())
+        });
+
+        let answers = self
+            .answers
+            .iter()
+            .map(|(part, answer)| format!("part{part}: {}", answer.as_deref().unwrap_or("...")))
+            .collect::<Vec<_>>()
+            .join("  ");
+
+        format!("{sim}\n{answers}")
+    }
+
+    fn draw(&self, frame: &mut Frame<'_>) {
+        let [status, view] =
+            Layout::vertical([Constraint::Length(4), Constraint::Min(0)]).areas(frame.area());
+
+        let title = format!("day{:02}", self.day);
+        frame.render_widget(
+            Paragraph::new(self.status()).block(Block::bordered().title(title)),
+            status,
+        );
+
+        let lines = self
+            .sim
+            .as_ref()
+            .map_or_else(|| self.input.clone(), |it| it.view());
+        frame.render_widget(
+            Paragraph::new(lines.join("\n"))
+                .scroll(self.scroll)
+                .block(Block::bordered().title(HELP)),
+            view,
+        );
+    }
+
+    fn run(mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        loop {
+            self.tick();
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if !event::poll(Duration::from_millis(50))? {
+                continue;
+            }
+
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            let (y, x) = &mut self.scroll;
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => {
+                    self.solving.cancel();
+                    return Ok(());
+                }
+                KeyCode::Char(' ') => self.step(),
+                KeyCode::Char('r') => self.running = !self.running && self.sim.is_some(),
+                KeyCode::Up => *y = y.saturating_sub(1),
+                KeyCode::Down => *y = y.saturating_add(1),
+                KeyCode::Left => *x = x.saturating_sub(1),
+                KeyCode::Right => *x = x.saturating_add(1),
+                KeyCode::PageUp => *y = y.saturating_sub(20),
+                KeyCode::PageDown => *y = y.saturating_add(20),
+                _ => {}
+            }
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    // the raw-mode screen has no room for panic messages, the failed part shows it instead
+    let report = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let name = thread::current().name().map(String::from);
+        if !name.is_some_and(|it| it.starts_with(SOLVER_THREAD)) {
+            report(info);
+        }
+    }));
+
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+
+    let Some(day) = args.first().and_then(|it| it.parse::<u32>().ok()) else {
+        eprintln!("usage: explore <day> [input file]");
+        return ExitCode::from(2);
+    };
+
+    let path = args
+        .get(1)
+        .cloned()
+        .unwrap_or_else(|| format!("input/2023/day{day}.txt"));
+
+    let inp = match std::fs::read_to_string(&path) {
+        Ok(inp) => inp,
+        Err(e) => {
+            eprintln!("failed to read {path}: {e}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let app = match App::new(day, &inp) {
+        Ok(app) => app,
+        Err(e) => {
+            eprintln!("{path}: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut terminal = ratatui::init();
+    let result = app.run(&mut terminal);
+    ratatui::restore();
+
+    if let Err(e) = result {
+        eprintln!("{e}");
+        return ExitCode::FAILURE;
+    }
+
+    ExitCode::SUCCESS
+}
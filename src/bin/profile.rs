@@ -0,0 +1,212 @@
+use aoc_2023::registry::{self, Context, Params};
+use aoc_utils::cancel::Token;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::process::ExitCode;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+// Forwards to the system allocator and keeps count of what goes through it.
+struct Counting;
+
+impl Counting {
+    fn record_alloc(size: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED.fetch_add(size, Ordering::Relaxed);
+        let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(live, Ordering::Relaxed);
+    }
+
+    fn record_dealloc(size: usize) {
+        LIVE.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+// SAFETY: every method hands its arguments straight to `System`, which upholds the
+// `GlobalAlloc` contract; the counters only touch atomics and never allocate themselves.
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        // SAFETY: the caller's guarantees about `layout` carry over unchanged
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        // SAFETY: as for `alloc`
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: `ptr` came from this allocator, i.e. from `System`, with this `layout`
+        System.dealloc(ptr, layout);
+        Self::record_dealloc(layout.size());
+    }
+
+    // counted as a fresh allocation of the new size, like a naive grow would be
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        // SAFETY: as for `dealloc`, and the caller vouches for `new_size`
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Self::record_dealloc(layout.size());
+            Self::record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+struct Usage {
+    time: Duration,
+    allocations: usize,
+    allocated: usize,
+    // highest heap usage above what was live when the measurement started
+    peak: usize,
+}
+
+fn measure<T>(f: impl FnOnce() -> T) -> (T, Usage) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let allocated = ALLOCATED.load(Ordering::Relaxed);
+    let live = LIVE.load(Ordering::Relaxed);
+    PEAK.store(live, Ordering::Relaxed);
+
+    let start = Instant::now();
+    let result = f();
+    let time = start.elapsed();
+
+    let usage = Usage {
+        time,
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        allocated: ALLOCATED.load(Ordering::Relaxed) - allocated,
+        peak: PEAK.load(Ordering::Relaxed).saturating_sub(live),
+    };
+
+    (result, usage)
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn bytes(n: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut value = n as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{n} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+fn print_row(solver: &str, phase: &str, usage: &Usage) {
+    println!(
+        "{solver:<24} {phase:<9} {:>12.3?} {:>10} {:>11} {:>11}",
+        usage.time,
+        usage.allocations,
+        bytes(usage.allocated),
+        bytes(usage.peak)
+    );
+}
+
+// high-water mark of the resident set, as reported by Linux
+fn peak_resident() -> Option<String> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|it| it.starts_with("VmHWM:"))?;
+
+    Some(line.trim_start_matches("VmHWM:").trim().to_string())
+}
+
+const USAGE: &str = "usage: profile [--input FILE] [day ...]";
+
+fn main() -> ExitCode {
+    #[cfg(feature = "trace")]
+    let _trace = match aoc_2023::trace::init() {
+        Ok(guard) => guard,
+        Err(e) => {
+            eprintln!("failed to set up tracing: {e}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut input = None;
+    let mut days = vec![];
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--input" {
+            let Some(path) = args.next() else {
+                eprintln!("{USAGE}");
+                return ExitCode::from(2);
+            };
+            input = Some(path);
+        } else if let Ok(day) = arg.parse::<u32>() {
+            days.push(day);
+        } else {
+            eprintln!("{USAGE}");
+            return ExitCode::from(2);
+        }
+    }
+
+    // another input only makes sense for the one day it belongs to
+    if input.is_some() && days.len() != 1 {
+        eprintln!("--input needs exactly one day");
+        return ExitCode::from(2);
+    }
+    if days.is_empty() {
+        days = (1..=25).collect();
+    }
+
+    println!(
+        "{:<24} {:<9} {:>12} {:>10} {:>11} {:>11}",
+        "solver", "phase", "time", "allocs", "allocated", "peak heap"
+    );
+
+    for day in days {
+        let path = input
+            .clone()
+            .unwrap_or_else(|| format!("input/2023/day{day}.txt"));
+        let Ok(inp) = std::fs::read_to_string(&path) else {
+            eprintln!("skipping day {day}: failed to read {path}");
+            continue;
+        };
+
+        // cargo-aoc strips trailing newlines before handing the input to the generators
+        let inp = inp.trim_end_matches('\n');
+
+        for solver in registry::solvers().into_iter().filter(|it| it.day == day) {
+            let name = format!("day{day:02} part{} {}", solver.part, solver.name);
+
+            let (part, usage) = measure(|| solver.prepare(inp, &Params::default()));
+            print_row(&name, "generate", &usage);
+
+            let (answer, usage) = measure(|| part(&Context::new(&Token::new())));
+            print_row(&name, "solve", &usage);
+
+            if let Err(e) = answer {
+                eprintln!("{name}: {e}");
+            }
+        }
+    }
+
+    if let Some(peak) = peak_resident() {
+        println!("peak resident memory: {peak}");
+    }
+
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,99 @@
+use aoc_2023::report::{self, Record, CSV_HEADER};
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: report [--format json|ndjson|csv] [--no-timings] [day ...]";
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Format {
+    Json,
+    Ndjson,
+    Csv,
+}
+
+fn print(records: &[Record], format: Format) {
+    match format {
+        // one record per line, so the output diffs well
+        Format::Json => {
+            println!("[");
+            for (idx, record) in records.iter().enumerate() {
+                let sep = if idx + 1 < records.len() { "," } else { "" };
+                println!("  {}{sep}", record.to_json());
+            }
+            println!("]");
+        }
+        Format::Ndjson => {
+            for record in records {
+                println!("{}", record.to_json());
+            }
+        }
+        Format::Csv => {
+            println!("{CSV_HEADER}");
+            for record in records {
+                println!("{}", record.to_csv());
+            }
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    #[cfg(feature = "trace")]
+    let _trace = match aoc_2023::trace::init() {
+        Ok(guard) => guard,
+        Err(e) => {
+            eprintln!("failed to set up tracing: {e}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut format = Format::Ndjson;
+    let mut timings = true;
+    let mut days = vec![];
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => {
+                format = match args.next().as_deref() {
+                    Some("json") => Format::Json,
+                    Some("ndjson") => Format::Ndjson,
+                    Some("csv") => Format::Csv,
+                    _ => {
+                        eprintln!("{USAGE}");
+                        return ExitCode::from(2);
+                    }
+                }
+            }
+            "--no-timings" => timings = false,
+            _ => {
+                let Ok(day) = arg.parse::<u32>() else {
+                    eprintln!("{USAGE}");
+                    return ExitCode::from(2);
+                };
+                days.push(day);
+            }
+        }
+    }
+
+    if days.is_empty() {
+        days = (1..=25).collect();
+    }
+
+    let mut records = vec![];
+    for day in days {
+        let path = format!("input/2023/day{day}.txt");
+        let Ok(inp) = std::fs::read_to_string(&path) else {
+            eprintln!("skipping day {day}: failed to read {path}");
+            continue;
+        };
+
+        records.extend(report::run(day, &inp, timings));
+    }
+
+    print(&records, format);
+
+    if records.iter().any(|it| it.error.is_some()) {
+        return ExitCode::FAILURE;
+    }
+
+    ExitCode::SUCCESS
+}
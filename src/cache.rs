@@ -0,0 +1,162 @@
+use crate::report::checksum;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::convert::Infallible;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// Setting this to a directory turns the cache on; without it everything is recomputed.
+pub const DIR_VAR: &str = "AOC_CACHE_DIR";
+
+// Expensive intermediate results, stored with bincode and keyed by day, stage, input,
+// result type and a hash of the sources at build time. Changing any of those misses the
+// old entries, which stay around until the directory is cleared.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    #[must_use]
+    pub fn new(dir: &Path) -> Self {
+        Self {
+            dir: dir.to_path_buf(),
+        }
+    }
+
+    #[must_use]
+    pub fn from_env() -> Option<Self> {
+        std::env::var_os(DIR_VAR).map(|it| Self::new(Path::new(&it)))
+    }
+
+    fn path<T>(&self, day: u32, stage: &str, inp: &str) -> PathBuf {
+        let key = checksum(&format!(
+            "{}\n{}\n{inp}",
+            env!("AOC_SOURCE_HASH"),
+            std::any::type_name::<T>()
+        ));
+
+        self.dir.join(format!("day{day:02}-{stage}-{key}.bin"))
+    }
+
+    // `None` for a missing or unreadable entry
+    #[must_use]
+    pub fn load<T: DeserializeOwned>(&self, day: u32, stage: &str, inp: &str) -> Option<T> {
+        let bytes = std::fs::read(self.path::<T>(day, stage, inp)).ok()?;
+        bincode::deserialize(&bytes).ok()
+    }
+
+    // written to a temporary file first, so a concurrent load never sees half an entry
+    pub fn store<T: Serialize>(
+        &self,
+        day: u32,
+        stage: &str,
+        inp: &str,
+        value: &T,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        static WRITES: AtomicUsize = AtomicUsize::new(0);
+
+        std::fs::create_dir_all(&self.dir)?;
+
+        let path = self.path::<T>(day, stage, inp);
+        let write = WRITES.fetch_add(1, Ordering::Relaxed);
+        let tmp = path.with_extension(format!("{}-{write}.tmp", std::process::id()));
+
+        std::fs::write(&tmp, bincode::serialize(value)?)?;
+        if let Err(e) = std::fs::rename(&tmp, &path) {
+            let _ = std::fs::remove_file(&tmp);
+            return Err(e.into());
+        }
+
+        Ok(())
+    }
+}
+
+// Runs `compute` on `inp`, unless the cache is on and already has its result. The cache
+// only ever speeds things up, so failing to write to it is only logged.
+pub fn cached<T: Serialize + DeserializeOwned>(
+    day: u32,
+    stage: &str,
+    inp: &str,
+    compute: impl FnOnce(&str) -> T,
+) -> T {
+    match try_cached(day, stage, inp, |it| Ok::<_, Infallible>(compute(it))) {
+        Ok(value) => value,
+        Err(e) => match e {},
+    }
+}
+
+// like `cached`, for stages that can fail; failures aren't cached
+pub fn try_cached<T, E>(
+    day: u32,
+    stage: &str,
+    inp: &str,
+    compute: impl FnOnce(&str) -> Result<T, E>,
+) -> Result<T, E>
+where
+    T: Serialize + DeserializeOwned,
+{
+    let Some(cache) = Cache::from_env() else {
+        return compute(inp);
+    };
+
+    if let Some(value) = cache.load(day, stage, inp) {
+        tracing::debug!(day, stage, "cache hit");
+        return Ok(value);
+    }
+
+    tracing::debug!(day, stage, "cache miss");
+    let value = compute(inp)?;
+    if let Err(e) = cache.store(day, stage, inp, &value) {
+        tracing::warn!(day, stage, error = %e, "failed to store cache entry");
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_store_and_load() {
+        let dir = std::env::temp_dir().join(format!("aoc-cache-{}", std::process::id()));
+        let cache = Cache::new(&dir);
+
+        cache
+            .store(8, "generate", "a", &vec![1_u32, 2, 3])
+            .expect("store");
+        assert_eq!(
+            cache.load::<Vec<u32>>(8, "generate", "a"),
+            Some(vec![1, 2, 3])
+        );
+        assert_eq!(cache.load::<Vec<u32>>(8, "generate", "b"), None);
+        assert_eq!(cache.load::<Vec<u64>>(8, "generate", "a"), None);
+
+        // every input keeps its own entry, and no temporary files are left behind
+        cache
+            .store(8, "generate", "b", &vec![4_u32])
+            .expect("store");
+        assert_eq!(
+            cache.load::<Vec<u32>>(8, "generate", "a"),
+            Some(vec![1, 2, 3])
+        );
+        assert_eq!(cache.load::<Vec<u32>>(8, "generate", "b"), Some(vec![4]));
+        assert_eq!(std::fs::read_dir(&dir).expect("cache dir").count(), 2);
+
+        std::fs::remove_dir_all(&dir).expect("clean up");
+    }
+
+    #[test]
+    fn test_corrupt_entry_is_a_miss() {
+        let dir = std::env::temp_dir().join(format!("aoc-cache-corrupt-{}", std::process::id()));
+        let cache = Cache::new(&dir);
+
+        cache
+            .store(20, "generate", "a", &"x".repeat(8))
+            .expect("store");
+        std::fs::write(cache.path::<String>(20, "generate", "a"), [0xff]).expect("corrupt");
+        assert_eq!(cache.load::<String>(20, "generate", "a"), None);
+
+        std::fs::remove_dir_all(&dir).expect("clean up");
+    }
+}
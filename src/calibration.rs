@@ -0,0 +1,896 @@
+use aho_corasick::{AhoCorasick, AhoCorasickBuilder, BuildError, Match};
+use aoc_utils::checked::{self, Overflow};
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Seek, SeekFrom};
+use std::ops::Range;
+use std::panic;
+use std::path::Path;
+
+const DIGITS: [&str; 10] = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+
+const ENGLISH: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+// The ASCII digits plus words spelling them, all matched at once by an Aho-Corasick
+// automaton. Matches may overlap, so `eightwo` has both an 8 and a 2.
+#[derive(Clone, Debug)]
+pub struct DigitVocabulary {
+    automaton: AhoCorasick,
+    // by pattern id, the digits first
+    values: Vec<u32>,
+    whole_words: bool,
+}
+
+impl DigitVocabulary {
+    // Case insensitive matching only folds ASCII letters, so list other spellings, e.g.
+    // `Fünf` next to `fünf`, as words of their own.
+    pub fn new<'w>(
+        words: impl IntoIterator<Item = (&'w str, u32)>,
+        case_insensitive: bool,
+    ) -> Result<Self, Box<dyn Error>> {
+        let words = words.into_iter().collect::<Vec<_>>();
+        if let Some((word, value)) = words.iter().find(|(_, value)| *value > 9) {
+            return Err(format!("`{word}` spells {value}, which is not a digit").into());
+        }
+
+        Ok(Self::build(words, case_insensitive)?)
+    }
+
+    // like `new`, for words of any value
+    fn build<'w>(
+        words: impl IntoIterator<Item = (&'w str, u32)>,
+        case_insensitive: bool,
+    ) -> Result<Self, BuildError> {
+        let (mut patterns, mut values) = (DIGITS.to_vec(), (0..10).collect::<Vec<_>>());
+        for (word, value) in words {
+            patterns.push(word);
+            values.push(value);
+        }
+
+        let automaton = AhoCorasickBuilder::new()
+            .ascii_case_insensitive(case_insensitive)
+            .build(patterns)?;
+
+        Ok(Self {
+            automaton,
+            values,
+            whole_words: false,
+        })
+    }
+
+    // Words only count where no letter or digit touches them, so `often` holds no `ten`
+    // and `eightwo` no digit at all. The digits themselves still count anywhere.
+    #[must_use]
+    pub const fn whole_words(mut self) -> Self {
+        self.whole_words = true;
+        self
+    }
+
+    // only the digits themselves
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn digits() -> Self {
+        Self::new([], false).expect("digits")
+    }
+
+    // the digits and `one` to `nine`
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn english() -> Self {
+        Self::new(ENGLISH.into_iter().zip(1..), false).expect("english words")
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum TokenKind {
+    Digit,
+    Word,
+}
+
+// a match in a line, `span` in bytes
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Token {
+    pub value: u32,
+    pub span: Range<usize>,
+    pub kind: TokenKind,
+}
+
+// whether the letters around `span` leave it a word of its own
+fn stands_alone(s: &str, span: &Range<usize>) -> bool {
+    let before = s[..span.start].chars().next_back();
+    let after = s[span.end..].chars().next();
+
+    !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+}
+
+impl DigitVocabulary {
+    fn token(&self, m: &Match) -> Token {
+        Token {
+            value: self.values[m.pattern()],
+            span: m.range(),
+            kind: if m.pattern().as_usize() < DIGITS.len() {
+                TokenKind::Digit
+            } else {
+                TokenKind::Word
+            },
+        }
+    }
+
+    // every match in `s`, overlapping ones included, in the order they end
+    fn tokens<'a>(&'a self, s: &'a str) -> impl Iterator<Item = Token> + 'a {
+        self.automaton
+            .find_overlapping_iter(s)
+            .map(|it| self.token(&it))
+            .filter(|it| {
+                !self.whole_words || it.kind == TokenKind::Digit || stands_alone(s, &it.span)
+            })
+    }
+}
+
+// the digit starting first
+#[must_use]
+pub fn find_digit(s: &str, vocabulary: &DigitVocabulary) -> Option<Token> {
+    vocabulary.tokens(s).min_by_key(|it| it.span.start)
+}
+
+// the digit ending last
+#[must_use]
+pub fn rfind_digit(s: &str, vocabulary: &DigitVocabulary) -> Option<Token> {
+    vocabulary.tokens(s).max_by_key(|it| it.span.end)
+}
+
+// how a line got its calibration value
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Explanation {
+    pub first: Token,
+    pub last: Token,
+    pub value: u32,
+}
+
+// `None` for a line without any digit
+#[must_use]
+pub fn explain(line: &str, vocabulary: &DigitVocabulary) -> Option<Explanation> {
+    let first = find_digit(line, vocabulary)?;
+    let last = rfind_digit(line, vocabulary)?;
+    let value = first.value * 10 + last.value;
+
+    Some(Explanation { first, last, value })
+}
+
+// the first and last digit as a two digit number, `None` without any digit
+#[must_use]
+pub fn calibration_value(line: &str, vocabulary: &DigitVocabulary) -> Option<u32> {
+    explain(line, vocabulary).map(|it| it.value)
+}
+
+const SMALL: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+const TENS: [&str; 8] = [
+    "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+const SCALES: [(&str, u32); 3] = [
+    ("thousand", 1_000),
+    ("million", 1_000_000),
+    ("billion", 1_000_000_000),
+];
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum NumberWord {
+    // a single character of a numeral like `104`
+    Digit(u64),
+    // zero to nineteen
+    Small(u64),
+    Tens(u64),
+    Hundred,
+    Scale(u64),
+}
+
+impl NumberWord {
+    fn of(token: &Token) -> Self {
+        let value = u64::from(token.value);
+        match (token.kind, value) {
+            (TokenKind::Digit, _) => Self::Digit(value),
+            (TokenKind::Word, 0..=19) => Self::Small(value),
+            (TokenKind::Word, 20..=99) => Self::Tens(value),
+            (TokenKind::Word, 100) => Self::Hundred,
+            (TokenKind::Word, _) => Self::Scale(value),
+        }
+    }
+}
+
+// Whole English numbers like `zero`, `twenty-three` or `one hundred and four`, and
+// numerals like `104`. Words match ASCII case insensitively, and only as whole words, so
+// `seventeen` is never read as `seven` and `often` holds no `ten`.
+#[derive(Clone, Debug)]
+pub struct NumberPhrases {
+    vocabulary: DigitVocabulary,
+}
+
+impl NumberPhrases {
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn english() -> Self {
+        let words = SMALL
+            .into_iter()
+            .zip(0..)
+            .chain(TENS.into_iter().zip((20..).step_by(10)))
+            .chain([("hundred", 100)])
+            .chain(SCALES);
+
+        let vocabulary = DigitVocabulary::build(words, true)
+            .expect("english number words")
+            .whole_words();
+
+        Self { vocabulary }
+    }
+}
+
+// a whole number in a line, `span` in bytes
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Number {
+    pub value: u64,
+    pub span: Range<usize>,
+    // digits as written, so `007` keeps its zeros
+    pub width: usize,
+}
+
+const fn decimal_width(value: u64) -> usize {
+    match value.checked_ilog10() {
+        Some(it) => it as usize + 1,
+        None => 1,
+    }
+}
+
+// whitespace and hyphens only, like in `twenty-three` or `one hundred`
+fn is_blank(gap: &str) -> bool {
+    gap.trim_matches(|it: char| it.is_whitespace() || it == '-')
+        .is_empty()
+}
+
+#[derive(Clone, Debug)]
+struct Phrase {
+    span: Range<usize>,
+    // the part above `current`, made of finished thousands, millions and billions
+    total: u64,
+    current: u64,
+    last: NumberWord,
+    smallest_scale: Option<u64>,
+    // whether `current` already has its hundreds
+    has_hundred: bool,
+    // the words after the last hundred or scale word
+    units: Option<Units>,
+}
+
+#[derive(Clone, Debug)]
+struct Units {
+    start: usize,
+    // where the phrase ended before them, and on which word
+    before: usize,
+    last: NumberWord,
+    value: u64,
+}
+
+impl Phrase {
+    const fn start(word: NumberWord, span: Range<usize>) -> Self {
+        let (total, current, smallest_scale) = match word {
+            NumberWord::Digit(value) | NumberWord::Small(value) | NumberWord::Tens(value) => {
+                (0, value, None)
+            }
+            // `hundred and one` for a hundred and one
+            NumberWord::Hundred => (0, 100, None),
+            NumberWord::Scale(scale) => (scale, 0, Some(scale)),
+        };
+
+        Self {
+            span,
+            total,
+            current,
+            last: word,
+            smallest_scale,
+            has_hundred: matches!(word, NumberWord::Hundred),
+            units: None,
+        }
+    }
+
+    // whether `word`, after `gap`, still belongs to this number
+    fn continues(&self, gap: &str, word: NumberWord) -> bool {
+        use NumberWord::{Digit, Hundred, Scale, Small, Tens};
+
+        if matches!(self.last, Digit(_)) || matches!(word, Digit(_)) {
+            return matches!((self.last, word), (Digit(_), Digit(_))) && gap.is_empty();
+        }
+
+        let trimmed = gap.trim_matches(|it: char| it.is_whitespace() || it == '-');
+        let gap_fits = is_blank(gap)
+            || (trimmed.eq_ignore_ascii_case("and") && matches!(self.last, Hundred | Scale(_)));
+
+        let word_fits = match (self.last, word) {
+            (Small(_), Hundred) => !self.has_hundred,
+            (Hundred | Scale(_), Small(_) | Tens(_)) => true,
+            (Tens(_), Small(value)) => (1..10).contains(&value),
+            (Small(_) | Tens(_) | Hundred, Scale(scale)) => {
+                self.smallest_scale.is_none_or(|it| scale < it)
+            }
+            _ => false,
+        };
+
+        gap_fits && word_fits
+    }
+
+    fn push(&mut self, word: NumberWord, span: &Range<usize>) -> Result<(), Overflow> {
+        match word {
+            NumberWord::Digit(value) => {
+                self.current = checked::add(&checked::mul(&self.current, &10)?, &value)?;
+            }
+            NumberWord::Small(value) | NumberWord::Tens(value) => {
+                self.current = checked::add(&self.current, &value)?;
+                if self.has_hundred || self.smallest_scale.is_some() {
+                    self.units = Some(match self.units.take() {
+                        Some(units) => Units {
+                            value: units.value + value,
+                            ..units
+                        },
+                        None => Units {
+                            start: span.start,
+                            before: self.span.end,
+                            last: self.last,
+                            value,
+                        },
+                    });
+                }
+            }
+            NumberWord::Hundred => {
+                self.current = checked::mul(&self.current, &100)?;
+                self.has_hundred = true;
+                self.units = None;
+            }
+            NumberWord::Scale(scale) => {
+                self.total = checked::add(&self.total, &checked::mul(&self.current, &scale)?)?;
+                self.current = 0;
+                self.smallest_scale = Some(scale);
+                self.has_hundred = false;
+                self.units = None;
+            }
+        }
+
+        self.last = word;
+        self.span.end = span.end;
+        Ok(())
+    }
+
+    // the words after the last hundred or scale word, as a phrase of their own
+    fn split_off_units(&mut self) -> Option<Self> {
+        let units = self.units.take()?;
+        let rest = Self {
+            span: units.start..self.span.end,
+            total: 0,
+            current: units.value,
+            last: self.last,
+            smallest_scale: None,
+            has_hundred: false,
+            units: None,
+        };
+
+        self.span.end = units.before;
+        self.current -= units.value;
+        self.last = units.last;
+        Some(rest)
+    }
+
+    fn finish(self) -> Result<Number, Overflow> {
+        let value = checked::add(&self.total, &self.current)?;
+        let width = if matches!(self.last, NumberWord::Digit(_)) {
+            self.span.len()
+        } else {
+            decimal_width(value)
+        };
+
+        Ok(Number {
+            value,
+            span: self.span,
+            width,
+        })
+    }
+}
+
+// every number in `s`, in order; fails on numerals too large for a u64
+pub fn numbers(s: &str, phrases: &NumberPhrases) -> Result<Vec<Number>, Overflow> {
+    let mut result = vec![];
+    let mut phrase: Option<Phrase> = None;
+
+    for token in phrases.vocabulary.tokens(s) {
+        let word = NumberWord::of(&token);
+
+        phrase = match phrase {
+            Some(mut p) if p.continues(&s[p.span.end..token.span.start], word) => {
+                p.push(word, &token.span)?;
+                Some(p)
+            }
+            finished => {
+                let mut next = None;
+                if let Some(mut p) = finished {
+                    // `one hundred five hundred` is 100 and 500, not 10500, and
+                    // `one thousand two thousand` is 1000 and 2000
+                    if matches!(word, NumberWord::Hundred | NumberWord::Scale(_))
+                        && is_blank(&s[p.span.end..token.span.start])
+                    {
+                        next = p.split_off_units();
+                    }
+                    result.push(p.finish()?);
+                }
+
+                match next {
+                    Some(mut next) => {
+                        next.push(word, &token.span)?;
+                        Some(next)
+                    }
+                    None => Some(Phrase::start(word, token.span)),
+                }
+            }
+        };
+    }
+
+    if let Some(p) = phrase {
+        result.push(p.finish()?);
+    }
+
+    Ok(result)
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+pub enum Combine {
+    // the first and last number written one after the other, `23 .. 104` is 23104
+    #[default]
+    Numbers,
+    // the first digit of the first number and the last of the last, `23 .. 104` is 24
+    Digits,
+}
+
+impl Combine {
+    // the calibration value of a line from its first and last number
+    pub fn apply(self, first: &Number, last: &Number) -> Result<u64, Overflow> {
+        match self {
+            Self::Numbers => {
+                let shift = (0..last.width).try_fold(1u64, |acc, _| checked::mul(&acc, &10))?;
+                checked::add(&checked::mul(&first.value, &shift)?, &last.value)
+            }
+            Self::Digits => Ok(leading_digit(first) * 10 + last.value % 10),
+        }
+    }
+}
+
+// the zero of `07` counts
+const fn leading_digit(number: &Number) -> u64 {
+    if number.width > decimal_width(number.value) {
+        return 0;
+    }
+
+    let mut value = number.value;
+    while value >= 10 {
+        value /= 10;
+    }
+
+    value
+}
+
+// `None` for a line without any number
+pub fn number_calibration_value(
+    line: &str,
+    phrases: &NumberPhrases,
+    combine: Combine,
+) -> Result<Option<u64>, Overflow> {
+    let numbers = numbers(line, phrases)?;
+    let (Some(first), Some(last)) = (numbers.first(), numbers.last()) else {
+        return Ok(None);
+    };
+
+    combine.apply(first, last).map(Some)
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+pub struct Totals {
+    pub total: u64,
+    pub lines: usize,
+    // lines without any digit, which add nothing to the total
+    pub missing: usize,
+}
+
+impl Totals {
+    fn merge(&mut self, other: &Self) -> io::Result<()> {
+        self.total = checked::add(&self.total, &other.total).map_err(io::Error::other)?;
+        self.lines += other.lines;
+        self.missing += other.missing;
+        Ok(())
+    }
+}
+
+// Calibrates the lines starting in the first `limit` bytes of `reader`, which sits at
+// byte `offset` of the document. Line numbers passed to `on_missing` count from 1.
+fn calibrate_lines<R: BufRead>(
+    mut reader: R,
+    vocabulary: &DigitVocabulary,
+    offset: u64,
+    limit: u64,
+    mut on_missing: impl FnMut(usize),
+) -> io::Result<Totals> {
+    let mut totals = Totals::default();
+    let mut buf = vec![];
+    let mut consumed = 0;
+
+    while consumed < limit {
+        buf.clear();
+        let read = reader.read_until(b'\n', &mut buf)?;
+        if read == 0 {
+            break;
+        }
+
+        let at = offset + consumed;
+        consumed += read as u64;
+        totals.lines += 1;
+
+        let line = std::str::from_utf8(&buf).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("line at byte {at}: {e}"),
+            )
+        })?;
+        let line = line
+            .strip_suffix('\n')
+            .map_or(line, |it| it.strip_suffix('\r').unwrap_or(it));
+
+        if let Some(value) = calibration_value(line, vocabulary) {
+            totals.total = checked::add(&totals.total, &u64::from(value))
+                .map_err(|e| io::Error::other(format!("line at byte {at}: {e}")))?;
+        } else {
+            totals.missing += 1;
+            on_missing(totals.lines);
+        }
+    }
+
+    Ok(totals)
+}
+
+// One line at a time, so memory stays constant however long the document is.
+pub fn calibrate_reader<R: BufRead>(
+    reader: R,
+    vocabulary: &DigitVocabulary,
+    on_missing: impl FnMut(usize),
+) -> io::Result<Totals> {
+    calibrate_lines(reader, vocabulary, 0, u64::MAX, on_missing)
+}
+
+// the chunk `start..end` of the file, moved forward to whole lines
+fn calibrate_chunk(
+    path: &Path,
+    vocabulary: &DigitVocabulary,
+    mut start: u64,
+    end: u64,
+) -> io::Result<(Totals, Vec<usize>)> {
+    let mut reader = BufReader::new(File::open(path)?);
+
+    // the line running into the chunk belongs to the one before
+    if start > 0 {
+        reader.seek(SeekFrom::Start(start - 1))?;
+        start += reader.read_until(b'\n', &mut vec![])? as u64 - 1;
+    }
+
+    let mut missing = vec![];
+    let totals = calibrate_lines(reader, vocabulary, start, end.saturating_sub(start), |it| {
+        missing.push(it);
+    })?;
+
+    Ok((totals, missing))
+}
+
+// Splits the file into `jobs` chunks at line boundaries and calibrates them on their
+// own threads. Line numbers are only known once every chunk before is done, so unlike
+// `calibrate_reader` this keeps the missing ones in memory until the end.
+pub fn calibrate_file_parallel(
+    path: &Path,
+    vocabulary: &DigitVocabulary,
+    jobs: usize,
+    mut on_missing: impl FnMut(usize),
+) -> io::Result<Totals> {
+    let len = std::fs::metadata(path)?.len();
+    // no more workers than bytes, each chunk holds at least one
+    let jobs = u64::try_from(jobs).unwrap_or(u64::MAX).clamp(1, len.max(1));
+    let bounds = (0..=jobs).map(move |it| {
+        let bound = u128::from(len) * u128::from(it) / u128::from(jobs);
+        u64::try_from(bound).expect("at most len")
+    });
+
+    let chunks = std::thread::scope(|scope| {
+        // every worker has to be running before the first join
+        #[allow(clippy::needless_collect)]
+        let workers = bounds
+            .clone()
+            .zip(bounds.skip(1))
+            .map(|(start, end)| scope.spawn(move || calibrate_chunk(path, vocabulary, start, end)))
+            .collect::<Vec<_>>();
+
+        workers
+            .into_iter()
+            .map(|it| it.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+            .collect::<io::Result<Vec<_>>>()
+    })?;
+
+    let mut totals = Totals::default();
+    for (chunk, missing) in chunks {
+        for line in missing {
+            on_missing(totals.lines + line);
+        }
+        totals.merge(&chunk)?;
+    }
+
+    Ok(totals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overlapping_words() {
+        let english = DigitVocabulary::english();
+        assert_eq!(calibration_value("eightwothree", &english), Some(83));
+        assert_eq!(calibration_value("xtwone3four", &english), Some(24));
+        assert_eq!(calibration_value("zoneight234", &english), Some(14));
+        assert_eq!(calibration_value("oneight", &english), Some(18));
+        assert_eq!(calibration_value("seven", &english), Some(77));
+        assert_eq!(calibration_value("nothing", &english), None);
+
+        let digits = DigitVocabulary::digits();
+        assert_eq!(calibration_value("eightwo0three", &digits), Some(0));
+        assert_eq!(calibration_value("eightwo", &digits), None);
+    }
+
+    #[test]
+    fn test_custom_vocabulary() {
+        let german = DigitVocabulary::new(
+            [
+                ("null", 0),
+                ("eins", 1),
+                ("zwei", 2),
+                ("drei", 3),
+                ("fünf", 5),
+                ("Fünf", 5),
+            ],
+            true,
+        )
+        .expect("vocabulary");
+
+        assert_eq!(calibration_value("NULLzweiXdrei", &german), Some(3));
+        assert_eq!(calibration_value("Fünf und EINS", &german), Some(51));
+        assert_eq!(calibration_value("zweins", &german), Some(21));
+        assert_eq!(calibration_value("three", &german), None);
+
+        assert!(DigitVocabulary::new([("ten", 10)], false).is_err());
+
+        let whole = DigitVocabulary::english().whole_words();
+        assert_eq!(calibration_value("eightwo 3", &whole), Some(33));
+        assert_eq!(calibration_value("x-two one7", &whole), Some(27));
+        assert_eq!(calibration_value("someone", &whole), None);
+    }
+
+    #[test]
+    fn test_explain() {
+        let english = DigitVocabulary::english();
+        let token = |value, span, kind| Token { value, span, kind };
+
+        assert_eq!(
+            explain("zoneight234", &english),
+            Some(Explanation {
+                first: token(1, 1..4, TokenKind::Word),
+                last: token(4, 10..11, TokenKind::Digit),
+                value: 14,
+            })
+        );
+        assert_eq!(
+            explain("oneight", &english),
+            Some(Explanation {
+                first: token(1, 0..3, TokenKind::Word),
+                last: token(8, 2..7, TokenKind::Word),
+                value: 18,
+            })
+        );
+        assert_eq!(explain("nothing", &english), None);
+    }
+
+    #[test]
+    fn test_multi_byte_text() {
+        let english = DigitVocabulary::english();
+        assert_eq!(calibration_value("café1naïve2é", &english), Some(12));
+        assert_eq!(calibration_value("🎄two🎁x🎅nine🦌", &english), Some(29));
+        assert_eq!(calibration_value("ñ3ñ", &english), Some(33));
+        assert_eq!(calibration_value("éight🎄on€", &english), None);
+        assert_eq!(
+            find_digit("🎄🎄7🎄eight", &english).map(|it| it.span),
+            Some(8..9)
+        );
+        assert_eq!(
+            rfind_digit("🎄🎄7🎄eight🎄", &english).map(|it| it.span),
+            Some(13..18)
+        );
+
+        // words with multi-byte letters, right next to other multi-byte text
+        let french = DigitVocabulary::new([("un", 1), ("deux", 2), ("zéro", 0), ("ZÉRO", 0)], true)
+            .expect("vocabulary");
+        assert_eq!(calibration_value("😀zérodeuxé", &french), Some(2));
+        assert_eq!(calibration_value("àUNèZÉRO🙂", &french), Some(10));
+        assert_eq!(calibration_value("zèro", &french), None);
+    }
+
+    #[test]
+    fn test_number_phrases() {
+        let english = NumberPhrases::english();
+        let values = |s| {
+            numbers(s, &english)
+                .expect("no overflow")
+                .into_iter()
+                .map(|it| it.value)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(values("zero"), [0]);
+        assert_eq!(values("Twenty-Three and one hundred and four"), [23, 104]);
+        assert_eq!(values("seventeen sixty"), [17, 60]);
+        assert_eq!(values("one two, three"), [1, 2, 3]);
+        assert_eq!(values("twenty twenty"), [20, 20]);
+        assert_eq!(values("eleven hundred and ninety-nine"), [1199]);
+        assert_eq!(values("twenty-one hundred"), [2100]);
+        assert_eq!(
+            values("two million three hundred thousand and five"),
+            [2_300_005]
+        );
+        assert_eq!(values("one thousand, two thousand"), [1000, 2000]);
+        assert_eq!(values("one thousand two thousand"), [1000, 2000]);
+        assert_eq!(values("one thousand and two thousand"), [1000, 2000]);
+        assert_eq!(values("one million two million"), [1_000_000, 2_000_000]);
+        assert_eq!(
+            values("one million five hundred thousand two thousand"),
+            [1_500_000, 2000]
+        );
+        assert_eq!(values("page 104, line7 eight"), [104, 7, 8]);
+        assert_eq!(values("nothing here"), Vec::<u64>::new());
+        assert_eq!(
+            values("often someone on the network puts on weight"),
+            Vec::<u64>::new()
+        );
+        assert_eq!(values("one hundred five hundred"), [100, 500]);
+        assert_eq!(values("one hundred and twenty-five hundred"), [100, 2500]);
+
+        assert_eq!(
+            numbers("x twenty-one and 7", &english),
+            Ok(vec![
+                Number {
+                    value: 21,
+                    span: 2..12,
+                    width: 2
+                },
+                Number {
+                    value: 7,
+                    span: 17..18,
+                    width: 1
+                }
+            ])
+        );
+        assert!(numbers("99999999999999999999", &english).is_err());
+    }
+
+    #[test]
+    fn test_number_calibration_value() {
+        let english = NumberPhrases::english();
+        let line = "twenty-three apples, 7 pears and one hundred and four plums";
+
+        assert_eq!(
+            number_calibration_value(line, &english, Combine::Numbers),
+            Ok(Some(23104))
+        );
+        assert_eq!(
+            number_calibration_value(line, &english, Combine::Digits),
+            Ok(Some(24))
+        );
+        assert_eq!(
+            number_calibration_value("zero", &english, Combine::Numbers),
+            Ok(Some(0))
+        );
+        assert_eq!(
+            number_calibration_value("nine", &english, Combine::Digits),
+            Ok(Some(99))
+        );
+        assert_eq!(
+            number_calibration_value("nothing", &english, Combine::Numbers),
+            Ok(None)
+        );
+
+        // leading zeros are kept, whichever way the numbers are combined
+        assert_eq!(
+            number_calibration_value("1 then 007", &english, Combine::Numbers),
+            Ok(Some(1007))
+        );
+        assert_eq!(
+            number_calibration_value("05 then 3", &english, Combine::Digits),
+            Ok(Some(3))
+        );
+    }
+
+    #[test]
+    fn test_calibrate_reader() {
+        let inp = "1abc2\r\npqr3stu8vwx\n\nno digits\ntreb7uchet";
+        let mut missing = vec![];
+
+        let totals = calibrate_reader(inp.as_bytes(), &DigitVocabulary::digits(), |it| {
+            missing.push(it);
+        })
+        .expect("in memory");
+
+        assert_eq!(
+            totals,
+            Totals {
+                total: 12 + 38 + 77,
+                lines: 5,
+                missing: 2
+            }
+        );
+        assert_eq!(missing, [3, 4]);
+
+        let err = calibrate_reader(&b"1\n\xff2\n"[..], &DigitVocabulary::digits(), |_| ())
+            .expect_err("not UTF-8");
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_calibrate_file_parallel() {
+        let path = std::env::temp_dir().join(format!("aoc-calibration-{}", std::process::id()));
+        let inp = (0..200)
+            .map(|it| match it % 7 {
+                0 => "no digits here".to_string(),
+                1 => "🎄".repeat(it),
+                _ => format!("x{it}two{}", it % 10),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        std::fs::write(&path, &inp).expect("temp file");
+
+        let english = DigitVocabulary::english();
+        let mut expected_missing = vec![];
+        let expected = calibrate_reader(inp.as_bytes(), &english, |it| expected_missing.push(it))
+            .expect("in memory");
+        assert_eq!(expected.missing, 58);
+
+        for jobs in [1, 2, 3, 8, 1000, usize::MAX] {
+            let mut missing = vec![];
+            let totals = calibrate_file_parallel(&path, &english, jobs, |it| missing.push(it))
+                .expect("temp file");
+
+            assert_eq!(totals, expected, "{jobs} jobs");
+            assert_eq!(missing, expected_missing, "{jobs} jobs");
+        }
+
+        std::fs::remove_file(&path).expect("clean up");
+    }
+}
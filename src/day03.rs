@@ -1,4 +1,6 @@
 use aoc_runner_derive::{aoc, aoc_generator};
+use aoc_utils::checked::{self, Overflow};
+use aoc_utils::parse::{Error, Source};
 use itertools::Itertools;
 use regex::Regex;
 
@@ -43,26 +45,22 @@ impl ParsedInput {
     }
 }
 
-fn collect_numbers(inp: &str) -> Vec<ParsedNumber> {
+fn collect_numbers(source: &Source<'_>) -> Result<Vec<ParsedNumber>, Error> {
     let re = Regex::new(r"\d+").expect("valid regex");
 
-    inp.lines()
-        .enumerate()
-        .fold(vec![], |mut acc, (row_idx, row)| {
-            for match_result in re.find_iter(row) {
-                acc.push(ParsedNumber {
-                    row: row_idx,
-                    start: match_result.start(),
-                    end: match_result.end(),
-                    value: match_result
-                        .as_str()
-                        .parse::<usize>()
-                        .expect("matched regex"),
-                });
-            }
-
-            acc
-        })
+    let mut result = vec![];
+    for (row_idx, row) in source.text().lines().enumerate() {
+        for match_result in re.find_iter(row) {
+            result.push(ParsedNumber {
+                row: row_idx,
+                start: match_result.start(),
+                end: match_result.end(),
+                value: source.parse(match_result.as_str())?,
+            });
+        }
+    }
+
+    Ok(result)
 }
 
 fn collect_symbols(inp: &str) -> Vec<Symbol> {
@@ -85,11 +83,11 @@ fn collect_symbols(inp: &str) -> Vec<Symbol> {
 }
 
 #[aoc_generator(day03)]
-pub fn generate(inp: &str) -> ParsedInput {
-    let numbers = collect_numbers(inp);
+pub fn generate(inp: &str) -> Result<ParsedInput, Error> {
+    let numbers = collect_numbers(&Source::new(inp))?;
     let symbols = collect_symbols(inp);
 
-    ParsedInput { symbols, numbers }
+    Ok(ParsedInput { symbols, numbers })
 }
 
 const fn is_symbol(c: char) -> bool {
@@ -97,15 +95,17 @@ const fn is_symbol(c: char) -> bool {
 }
 
 #[aoc(day03, part1)]
-pub fn part1(inp: &ParsedInput) -> usize {
-    inp.numbers
-        .iter()
-        .filter(|&it| it.touches_any_symbol(&inp.symbols))
-        .fold(0, |acc, it| acc + it.value)
+pub fn part1(inp: &ParsedInput) -> Result<usize, Overflow> {
+    checked::sum(
+        inp.numbers
+            .iter()
+            .filter(|&it| it.touches_any_symbol(&inp.symbols))
+            .map(|it| it.value),
+    )
 }
 
 #[aoc(day03, part2)]
-pub fn part2(inp: &ParsedInput) -> usize {
+pub fn part2(inp: &ParsedInput) -> Result<usize, Overflow> {
     inp.gears()
         .filter_map(|it| {
             let nums = inp
@@ -115,35 +115,7 @@ pub fn part2(inp: &ParsedInput) -> usize {
                 .collect_vec();
             (nums.len() == 2).then(|| (nums[0], nums[1]))
         })
-        .fold(0, |acc, (l, r)| acc + l.value * r.value)
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const TEST_INPUT: &str = "467..114..\n\
-                              ...*......\n\
-                              ..35..633.\n\
-                              ......#...\n\
-                              617*......\n\
-                              .....+.58.\n\
-                              ..592.....\n\
-                              ......755.\n\
-                              ...$.*....\n\
-                              .664.598..";
-
-    #[test]
-    fn test_p1() {
-        let gen = generate(TEST_INPUT);
-        let res = part1(&gen);
-        assert_eq!(res, 4361);
-    }
-
-    #[test]
-    fn test_p2() {
-        let gen = generate(TEST_INPUT);
-        let res = part2(&gen);
-        assert_eq!(res, 467_835);
-    }
+        .try_fold(0, |acc, (l, r)| {
+            checked::add(&acc, &checked::mul(&l.value, &r.value)?)
+        })
 }
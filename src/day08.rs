@@ -1,90 +1,115 @@
+use crate::cache;
 use aoc_runner_derive::{aoc, aoc_generator};
-use itertools::Itertools;
-use num::Integer;
-use parse_display_derive::{Display, FromStr};
-use std::collections::HashMap;
-
-#[derive(Display, FromStr, PartialEq, Eq, Hash, Clone, Debug)]
-#[display("{name} = ({left}, {right})")]
-pub struct Node {
-    name: String,
-    left: String,
-    right: String,
-}
-
-#[derive(Display, FromStr, Copy, Clone, Debug)]
+use aoc_utils::cancel::{Interrupted, Token};
+use aoc_utils::checked;
+use aoc_utils::graph::{Graph, NodeId};
+use aoc_utils::parse::{Error as ParseError, Source};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::error::Error;
+
+#[derive(Serialize, Deserialize, Copy, Clone, Debug)]
 pub enum Instruction {
-    #[display("L")]
     Left,
-
-    #[display("R")]
     Right,
 }
 
-#[derive(Clone, Debug)]
+// every node has exactly two successors: [left, right]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct ParsedInput {
     insts: Vec<Instruction>,
-    nodes: HashMap<Node, (Node, Node)>,
+    nodes: Graph,
 }
 
-fn build_node_mapping(inp: &[Node]) -> HashMap<Node, (Node, Node)> {
-    let mut node_map = HashMap::new();
+// `AAA = (BBB, CCC)`, borrowed from the line
+fn parse_node<'a>(
+    source: &Source<'a>,
+    line: &'a str,
+) -> Result<(&'a str, &'a str, &'a str), ParseError> {
+    let (name, successors) = source.split_once(line, " = ")?;
+    let (left, right) = source.split_once(
+        successors.trim_start_matches('(').trim_end_matches(')'),
+        ", ",
+    )?;
+
+    Ok((name, left, right))
+}
 
-    for node in inp {
-        let left_node = inp
-            .iter()
-            .find(|it| it.name == node.left)
-            .expect("left node exist");
+fn parse(inp: &str) -> Result<ParsedInput, ParseError> {
+    let source = Source::new(inp);
+    let mut lines = inp.lines();
+    let first = lines.next().unwrap_or_default();
+    let insts = first
+        .char_indices()
+        .map(|(idx, it)| match it {
+            'L' => Ok(Instruction::Left),
+            'R' => Ok(Instruction::Right),
+            _ => Err(source.error(&first[idx..], format!("unknown instruction {it}"))),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if insts.is_empty() {
+        return Err(source.error(inp, "expected instructions"));
+    }
 
-        let right_node = inp
-            .iter()
-            .find(|it| it.name == node.right)
-            .expect("right node exists");
+    let mut nodes = Graph::new();
+    let mut defined = HashSet::new();
+    for line in lines.filter(|it| !it.is_empty()) {
+        let (name, left, right) = parse_node(&source, line)?;
+        if !defined.insert(name) {
+            return Err(source.error(name, format!("node {name} defined twice")));
+        }
+        for next in [left, right] {
+            nodes
+                .add_labelled_edge(name, next)
+                .map_err(|e| source.error(line, e))?;
+        }
+    }
 
-        node_map.insert(node.clone(), (left_node.clone(), right_node.clone()));
+    // a successor nobody defined would be a dead end
+    if let Some(it) = nodes.nodes().find(|&it| nodes.successors(it).len() != 2) {
+        let label = nodes.label(it);
+        return Err(source.error(inp, format!("node {label} is never defined")));
     }
 
-    node_map
+    Ok(ParsedInput { insts, nodes })
 }
 
 #[aoc_generator(day08)]
-pub fn generate(inp: &str) -> ParsedInput {
-    let mut lines = inp.lines();
-    let insts = lines
-        .next()
-        .expect("first line")
-        .chars()
-        .map(|it| it.to_string().parse::<Instruction>().expect("input"))
-        .collect_vec();
-
-    let nodes = lines
-        .filter(|it| !it.is_empty())
-        .map(|it| it.parse::<Node>().expect("node"))
-        .collect_vec();
-
-    let nodes = build_node_mapping(&nodes);
-
-    ParsedInput { insts, nodes }
+pub fn generate(inp: &str) -> Result<ParsedInput, ParseError> {
+    cache::try_cached(8, "generate", inp, parse)
 }
 
+// reports the number of steps taken when interrupted
 fn steps_until_target_node<P>(
-    from: &Node,
+    from: NodeId,
     insts: &[Instruction],
-    nodes: &HashMap<Node, (Node, Node)>,
+    nodes: &Graph,
     target_check: P,
-) -> usize
+    token: &Token,
+) -> Result<usize, Interrupted<usize>>
 where
-    P: Fn(&Node) -> bool,
+    P: Fn(&str) -> bool,
 {
+    let targets: Vec<bool> = nodes
+        .nodes()
+        .map(|it| target_check(nodes.label(it)))
+        .collect();
     let mut inst_iter = insts.iter().cycle();
     let mut cur_node = from;
 
     for step in 0.. {
-        if target_check(cur_node) {
-            return step;
+        if targets[cur_node as usize] {
+            return Ok(step);
         }
 
-        let (left, right) = nodes.get(cur_node).expect("known node");
+        // reading the clock costs more than a step
+        if step % 4096 == 0 {
+            token.check_at(step)?;
+        }
+
+        let &[left, right] = nodes.successors(cur_node) else {
+            unreachable!("the generator checked every node has two successors")
+        };
 
         cur_node = match inst_iter.next().expect("cycle") {
             Instruction::Left => left,
@@ -95,72 +120,43 @@ where
     unreachable!()
 }
 
-#[aoc(day08, part1)]
-pub fn part1(inp: &ParsedInput) -> usize {
-    let cur_node = inp
-        .nodes
-        .keys()
-        .find(|it| it.name == "AAA")
-        .expect("start node");
+// fails with either `Interrupted<usize>` or a missing start node
+pub fn part1_with(inp: &ParsedInput, token: &Token) -> Result<usize, Box<dyn Error>> {
+    let cur_node = inp.nodes.id("AAA").ok_or("no start node AAA")?;
+
+    Ok(steps_until_target_node(
+        cur_node,
+        &inp.insts,
+        &inp.nodes,
+        |it| it == "ZZZ",
+        token,
+    )?)
+}
 
-    steps_until_target_node(cur_node, &inp.insts, &inp.nodes, |it| it.name == "ZZZ")
+#[aoc(day08, part1)]
+pub fn part1(inp: &ParsedInput) -> Result<usize, Box<dyn Error>> {
+    part1_with(inp, &Token::new())
 }
 
-#[aoc(day08, part2)]
-pub fn part2(inp: &ParsedInput) -> usize {
-    let start_nodes = inp.nodes.keys().filter(|it| it.name.ends_with('A'));
+// fails with either `Interrupted<usize>` or `Overflow`
+pub fn part2_with(inp: &ParsedInput, token: &Token) -> Result<usize, Box<dyn Error>> {
+    let start_nodes = inp
+        .nodes
+        .nodes()
+        .filter(|&it| inp.nodes.label(it).ends_with('A'));
 
-    start_nodes
-        .map(|it| steps_until_target_node(it, &inp.insts, &inp.nodes, |it| it.name.ends_with('Z')))
-        .fold(1usize, |acc, it| acc.lcm(&it))
-}
+    let mut result = 1usize;
+    for start in start_nodes {
+        let steps =
+            steps_until_target_node(start, &inp.insts, &inp.nodes, |it| it.ends_with('Z'), token)?;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const TEST_INPUT: &str = "RL\n\
-                              \n\
-                              AAA = (BBB, CCC)\n\
-                              BBB = (DDD, EEE)\n\
-                              CCC = (ZZZ, GGG)\n\
-                              DDD = (DDD, DDD)\n\
-                              EEE = (EEE, EEE)\n\
-                              GGG = (GGG, GGG)\n\
-                              ZZZ = (ZZZ, ZZZ)";
-
-    const TEST_INPUT_2: &str = "LLR\n\
-                                \n\
-                                AAA = (BBB, BBB)\n\
-                                BBB = (AAA, ZZZ)\n\
-                                ZZZ = (ZZZ, ZZZ)";
-
-    const TEST_INPUT_P2: &str = "LR\n\
-                                 \n\
-                                 11A = (11B, XXX)\n\
-                                 11B = (XXX, 11Z)\n\
-                                 11Z = (11B, XXX)\n\
-                                 22A = (22B, XXX)\n\
-                                 22B = (22C, 22C)\n\
-                                 22C = (22Z, 22Z)\n\
-                                 22Z = (22B, 22B)\n\
-                                 XXX = (XXX, XXX)";
-
-    #[test]
-    fn test_p1() {
-        let gen = generate(TEST_INPUT);
-        let res = part1(&gen);
-        assert_eq!(res, 2);
-
-        let gen = generate(TEST_INPUT_2);
-        let res = part1(&gen);
-        assert_eq!(res, 6);
+        result = checked::lcm(&result, &steps)?;
     }
 
-    #[test]
-    fn test_p2() {
-        let gen = generate(TEST_INPUT_P2);
-        let res = part2(&gen);
-        assert_eq!(res, 6);
-    }
+    Ok(result)
+}
+
+#[aoc(day08, part2)]
+pub fn part2(inp: &ParsedInput) -> Result<usize, Box<dyn Error>> {
+    part2_with(inp, &Token::new())
 }
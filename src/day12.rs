@@ -1,4 +1,8 @@
 use aoc_runner_derive::{aoc, aoc_generator};
+use aoc_utils::checked::{self, Overflow};
+use aoc_utils::parse::{Error, Source};
+use aoc_utils::progress::Progress;
+use aoc_utils::stats::Stats;
 use itertools::Itertools;
 use std::collections::HashMap;
 
@@ -9,28 +13,31 @@ pub struct ParsedInput {
 }
 
 #[aoc_generator(day12)]
-pub fn generate(inp: &str) -> ParsedInput {
+pub fn generate(inp: &str) -> Result<ParsedInput, Error> {
+    let source = Source::new(inp);
     let mut conditions = vec![];
     let mut records = vec![];
 
     for line in inp.lines() {
-        let (cond, rec) = line.split_once(' ').expect("whitespace");
+        let (cond, rec) = source.split_once(line, " ")?;
+        if let Some(idx) = cond.find(|it| !".#?".contains(it)) {
+            return Err(source.error(&cond[idx..], "unknown spring condition"));
+        }
         conditions.push(cond.chars().collect_vec());
         records.push(
             rec.split(',')
-                .map(|it| it.parse::<usize>().expect("input"))
-                .collect_vec(),
+                .map(|it| source.parse::<usize>(it))
+                .collect::<Result<_, _>>()?,
         );
     }
 
-    ParsedInput {
+    Ok(ParsedInput {
         conditions,
         records,
-    }
+    })
 }
 
-#[aoc(day12, part1)]
-pub fn part1(inp: &ParsedInput) -> usize {
+pub fn part1_with<S: Stats + ?Sized>(inp: &ParsedInput, stats: &S) -> Result<usize, Overflow> {
     assert_eq!(inp.conditions.len(), inp.records.len());
 
     let mut result = 0;
@@ -41,10 +48,16 @@ pub fn part1(inp: &ParsedInput) -> usize {
 
         let mut map = HashMap::new();
 
-        result += count_valid_combinations(cur_cond.clone(), cur_rec.clone(), &mut map);
+        let count = count_valid_combinations(cur_cond.clone(), cur_rec.clone(), &mut map, stats)?;
+        result = checked::add(&result, &count)?;
     }
 
-    result
+    Ok(result)
+}
+
+#[aoc(day12, part1)]
+pub fn part1(inp: &ParsedInput) -> Result<usize, Overflow> {
+    part1_with(inp, &())
 }
 
 fn is_valid_so_far(s: &[char], r: &[usize]) -> bool {
@@ -82,26 +95,30 @@ fn extract_groups(s: &[char]) -> Vec<Vec<char>> {
         .collect_vec()
 }
 
-fn count_valid_combinations(
+fn count_valid_combinations<S: Stats + ?Sized>(
     s: Vec<char>,
     r: Vec<usize>,
     cache: &mut HashMap<Vec<Vec<char>>, usize>,
-) -> usize {
+    stats: &S,
+) -> Result<usize, Overflow> {
     let groups = extract_groups(&s);
     if let Some(cached) = cache.get(&groups) {
-        return *cached;
+        tracing::trace!(count = cached, "memo hit");
+        stats.add("memo_hits", 1);
+        return Ok(*cached);
     }
+    stats.add("memo_misses", 1);
 
     if !is_valid_so_far(&s, &r) {
         cache.insert(groups, 0);
-        return 0;
+        return Ok(0);
     }
 
     if let Some(idx) = s.iter().position(|it| *it == '?') {
         let count_with_dot = {
             let mut new_str = s.clone();
             new_str[idx] = '.';
-            let res = count_valid_combinations(new_str.clone(), r.clone(), cache);
+            let res = count_valid_combinations(new_str.clone(), r.clone(), cache, stats)?;
 
             let groups = extract_groups(&new_str);
             cache.insert(groups, res);
@@ -112,7 +129,7 @@ fn count_valid_combinations(
         let count_with_hash = {
             let mut new_str = s;
             new_str[idx] = '#';
-            let res = count_valid_combinations(new_str.clone(), r, cache);
+            let res = count_valid_combinations(new_str.clone(), r, cache, stats)?;
 
             let groups = extract_groups(&new_str);
             cache.insert(groups, res);
@@ -120,18 +137,23 @@ fn count_valid_combinations(
             res
         };
 
-        return count_with_dot + count_with_hash;
+        return checked::add(&count_with_dot, &count_with_hash);
     }
 
     cache.insert(groups, 1);
-    1
+    Ok(1)
 }
 
-#[aoc(day12, part2)]
-pub fn part2(inp: &ParsedInput) -> usize {
+// reports every finished row
+pub fn part2_with<P, S>(inp: &ParsedInput, progress: &P, stats: &S) -> Result<usize, Overflow>
+where
+    P: Progress + ?Sized,
+    S: Stats + ?Sized,
+{
     assert_eq!(inp.conditions.len(), inp.records.len());
 
     let mut result = 0;
+    progress.start(Some(inp.conditions.len()));
 
     for idx in 0..inp.conditions.len() {
         let cur_cond = &inp.conditions[idx];
@@ -141,10 +163,18 @@ pub fn part2(inp: &ParsedInput) -> usize {
         let new_recs = cur_rec.repeat(5);
 
         let mut cache = HashMap::new();
-        result += count_valid_combinations(combs, new_recs, &mut cache);
+        let count = count_valid_combinations(combs, new_recs, &mut cache, stats)?;
+        result = checked::add(&result, &count)?;
+        progress.advance(1);
     }
 
-    result
+    progress.finish();
+    Ok(result)
+}
+
+#[aoc(day12, part2)]
+pub fn part2(inp: &ParsedInput) -> Result<usize, Overflow> {
+    part2_with(inp, &(), &())
 }
 
 fn join_with_separator(v: &[char], repeat: &[char]) -> Vec<char> {
@@ -160,29 +190,3 @@ fn join_with_separator(v: &[char], repeat: &[char]) -> Vec<char> {
 
     result
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const TEST_INPUT: &str = "???.### 1,1,3\n\
-                              .??..??...?##. 1,1,3\n\
-                              ?#?#?#?#?#?#?#? 1,3,1,6\n\
-                              ????.#...#... 4,1,1\n\
-                              ????.######..#####. 1,6,5\n\
-                              ?###???????? 3,2,1";
-
-    #[test]
-    fn test_p1() {
-        let gen = generate(TEST_INPUT);
-        let res = part1(&gen);
-        assert_eq!(res, 21);
-    }
-
-    #[test]
-    fn test_p2() {
-        let gen = generate(TEST_INPUT);
-        let res = part2(&gen);
-        assert_eq!(res, 525_152);
-    }
-}
@@ -1,10 +1,15 @@
 use aoc_runner_derive::{aoc, aoc_generator};
+use aoc_utils::checked::{self, Overflow};
+use aoc_utils::grid;
+use aoc_utils::parse::{Error, Source};
 use itertools::iproduct;
 
 #[aoc_generator(day13)]
-pub fn generate(inp: &str) -> Vec<Vec<Vec<char>>> {
+pub fn generate(inp: &str) -> Result<Vec<Vec<Vec<char>>>, Error> {
+    let source = Source::new(inp);
+
     inp.split_terminator("\n\n")
-        .map(|it| it.lines().map(|line| line.chars().collect()).collect())
+        .map(|it| grid::parse_part(&source, it, ".#"))
         .collect()
 }
 
@@ -59,53 +64,23 @@ fn horizontal_reflections(map: &[Vec<char>], errors: usize) -> Option<usize> {
     find_reflection_point(map, errors).map(|it| it + 1)
 }
 
-#[aoc(day13, part1)]
-pub fn part1(inp: &[Vec<Vec<char>>]) -> usize {
-    inp.iter().fold(0, |acc, it| {
-        acc + vertical_reflections(it, 0).unwrap_or_default()
-            + 100 * horizontal_reflections(it, 0).unwrap_or_default()
+fn summarize(inp: &[Vec<Vec<char>>], errors: usize) -> Result<usize, Overflow> {
+    inp.iter().try_fold(0, |acc, it| {
+        let vertical = vertical_reflections(it, errors).unwrap_or_default();
+        let horizontal = horizontal_reflections(it, errors).unwrap_or_default();
+        checked::add(
+            &acc,
+            &checked::add(&vertical, &checked::mul(&100, &horizontal)?)?,
+        )
     })
 }
 
-#[aoc(day13, part2)]
-pub fn part2(inp: &[Vec<Vec<char>>]) -> usize {
-    inp.iter().fold(0, |acc, it| {
-        acc + vertical_reflections(it, 1).unwrap_or_default()
-            + 100 * horizontal_reflections(it, 1).unwrap_or_default()
-    })
+#[aoc(day13, part1)]
+pub fn part1(inp: &[Vec<Vec<char>>]) -> Result<usize, Overflow> {
+    summarize(inp, 0)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const TEST_INPUT: &str = "#.##..##.\n\
-                              ..#.##.#.\n\
-                              ##......#\n\
-                              ##......#\n\
-                              ..#.##.#.\n\
-                              ..##..##.\n\
-                              #.#.##.#.\n\
-                              \n\
-                              #...##..#\n\
-                              #....#..#\n\
-                              ..##..###\n\
-                              #####.##.\n\
-                              #####.##.\n\
-                              ..##..###\n\
-                              #....#..#";
-
-    #[test]
-    fn test_p1() {
-        let gen = generate(TEST_INPUT);
-        let res = part1(&gen);
-        assert_eq!(res, 405);
-    }
-
-    #[test]
-    fn test_p2() {
-        let gen = generate(TEST_INPUT);
-        let res = part2(&gen);
-        assert_eq!(res, 400);
-    }
+#[aoc(day13, part2)]
+pub fn part2(inp: &[Vec<Vec<char>>]) -> Result<usize, Overflow> {
+    summarize(inp, 1)
 }
@@ -1,10 +1,14 @@
 use aoc_runner_derive::{aoc, aoc_generator};
-use itertools::{iproduct, Itertools};
-use std::collections::HashSet;
+use aoc_utils::checked::{self, Overflow};
+use aoc_utils::cycles;
+use aoc_utils::grid;
+use aoc_utils::parse::Error;
+use aoc_utils::stats::Stats;
+use itertools::iproduct;
 
 #[aoc_generator(day14)]
-pub fn generate(inp: &str) -> Vec<Vec<char>> {
-    inp.lines().map(|line| line.chars().collect()).collect_vec()
+pub fn generate(inp: &str) -> Result<Vec<Vec<char>>, Error> {
+    grid::parse(inp, ".#O")
 }
 
 fn tilt_north(inp: &mut [Vec<char>]) {
@@ -60,93 +64,48 @@ fn tilt_east(inp: &mut [Vec<char>]) {
 }
 
 #[aoc(day14, part1)]
-pub fn part1(inp: &[Vec<char>]) -> usize {
+pub fn part1(inp: &[Vec<char>]) -> Result<usize, Overflow> {
     let mut inp = inp.to_owned();
     tilt_north(&mut inp);
     calculate_load(&inp)
 }
 
-fn calculate_load(inp: &[Vec<char>]) -> usize {
+pub fn calculate_load(inp: &[Vec<char>]) -> Result<usize, Overflow> {
     let mut result = 0;
 
     for (idx, row) in inp.iter().enumerate() {
-        result += (inp.len() - idx) * row.iter().filter(|&it| *it == 'O').count();
+        let rocks = row.iter().filter(|&it| *it == 'O').count();
+        result = checked::add(&result, &checked::mul(&(inp.len() - idx), &rocks)?)?;
     }
 
-    result
+    Ok(result)
 }
 
-fn simulate_round(inp: &mut [Vec<char>]) {
+pub fn simulate_round(inp: &mut [Vec<char>]) {
     tilt_north(inp);
     tilt_west(inp);
     tilt_south(inp);
     tilt_east(inp);
 }
 
-fn run_until_cache_hit(
-    cur_cycle: &mut usize,
-    grid: &mut [Vec<char>],
-    cache: &mut HashSet<Vec<Vec<char>>>,
-) {
-    while cache.insert(grid.to_vec()) {
-        simulate_round(grid);
-        *cur_cycle += 1;
-    }
+pub fn part2_with<S: Stats + ?Sized>(inp: &[Vec<char>], stats: &S) -> Result<usize, Overflow> {
+    let history = tracing::info_span!("cycle_detection").in_scope(|| {
+        cycles::find_cycle(inp.to_owned(), |grid| {
+            let mut next = grid.clone();
+            simulate_round(&mut next);
+            next
+        })
+    });
+
+    let cycle = history.cycle;
+    tracing::debug!(cycle.prefix, cycle.period, "found spin cycle");
+    stats.set("cycle_start", cycle.prefix);
+    stats.set("cycle_length", cycle.period);
+
+    calculate_load(history.state_at(1_000_000_000))
 }
 
 #[aoc(day14, part2)]
-pub fn part2(inp: &[Vec<char>]) -> usize {
-    let mut prev_round = inp.to_owned();
-
-    let mut cache = HashSet::new();
-    let mut cycle = 0;
-
-    // find the cycle's start idx
-    run_until_cache_hit(&mut cycle, &mut prev_round, &mut cache);
-
-    let cycle_start = cycle - 1;
-
-    // find the length of the cycle
-    cache.clear();
-    run_until_cache_hit(&mut cycle, &mut prev_round, &mut cache);
-
-    let cycle_len = cycle - 1 - cycle_start;
-
-    // run the remaining iterations
-    let remaining = (1_000_000_000 - cycle) % cycle_len;
-    for _ in 0..remaining {
-        simulate_round(&mut prev_round);
-    }
-
-    calculate_load(&prev_round)
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const TEST_INPUT: &str = "O....#....\n\
-                              O.OO#....#\n\
-                              .....##...\n\
-                              OO.#O....O\n\
-                              .O.....O#.\n\
-                              O.#..O.#.#\n\
-                              ..O..#O..O\n\
-                              .......O..\n\
-                              #....###..\n\
-                              #OO..#....";
-
-    #[test]
-    fn test_p1() {
-        let gen = generate(TEST_INPUT);
-        let res = part1(&gen);
-        assert_eq!(res, 136);
-    }
-
-    #[test]
-    fn test_p2() {
-        let gen = generate(TEST_INPUT);
-        let res = part2(&gen);
-        assert_eq!(res, 64);
-    }
+pub fn part2(inp: &[Vec<char>]) -> Result<usize, Overflow> {
+    part2_with(inp, &())
 }
@@ -1,9 +1,45 @@
 use aoc_runner_derive::{aoc, aoc_generator};
-use itertools::Itertools;
+use aoc_utils::checked::{self, Overflow};
+use aoc_utils::label::Label;
+use aoc_utils::parse::{Error, Source};
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Operation {
+    Remove,
+    Insert(usize),
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Step {
+    label: Label,
+    op: Operation,
+    // of the whole step, which is all part 1 needs
+    hash: usize,
+}
+
+fn parse_step<'a>(source: &Source<'a>, s: &'a str) -> Result<Step, Error> {
+    let idx = s
+        .find(['-', '='])
+        .ok_or_else(|| source.error(s, "expected `-` or `=`"))?;
+    let label = Label::new(&s[..idx]).map_err(|e| source.error(s, e))?;
+    let op = match s[idx..].split_at(1) {
+        ("-", "") => Operation::Remove,
+        ("=", focal) => Operation::Insert(source.parse(focal)?),
+        _ => return Err(source.error(&s[idx..], "unknown operation")),
+    };
+
+    Ok(Step {
+        label,
+        op,
+        hash: hash(s),
+    })
+}
 
 #[aoc_generator(day15)]
-pub fn generate(inp: &str) -> Vec<String> {
-    inp.split(',').map(ToString::to_string).collect()
+pub fn generate(inp: &str) -> Result<Vec<Step>, Error> {
+    let source = Source::new(inp);
+
+    inp.split(',').map(|it| parse_step(&source, it)).collect()
 }
 
 fn hash(c: &str) -> usize {
@@ -16,50 +52,40 @@ fn hash(c: &str) -> usize {
 }
 
 #[aoc(day15, part1)]
-pub fn part1(inp: &[String]) -> usize {
-    inp.iter().fold(0, |acc, it| acc + hash(it))
+pub fn part1(inp: &[Step]) -> Result<usize, Overflow> {
+    checked::sum(inp.iter().map(|it| it.hash))
 }
 
 #[derive(Clone, Debug)]
 struct BoxWithLens {
-    label: String,
+    label: Label,
     focal: usize,
 }
 
 #[aoc(day15, part2)]
-pub fn part2(inp: &[String]) -> usize {
+pub fn part2(inp: &[Step]) -> Result<usize, Overflow> {
     let mut boxes: Vec<Vec<BoxWithLens>> = Vec::with_capacity(256);
     boxes.resize(256, vec![]);
 
-    for s in inp {
-        let label = s.chars().take_while(|it| *it != '-' && *it != '=').join("");
-        let op = if s.contains('-') { '-' } else { '=' };
-
-        let box_num = hash(&label);
+    for &Step { label, op, .. } in inp {
+        let box_num = hash(label.as_str());
         assert!((0..256).contains(&box_num));
 
         match op {
-            '=' => {
+            Operation::Insert(focal) => {
                 let b = &mut boxes[box_num];
-                let focal = s
-                    .chars()
-                    .filter(char::is_ascii_digit)
-                    .join("")
-                    .parse::<usize>()
-                    .expect("number");
                 if let Some(slot) = b.iter_mut().find(|it| it.label == label) {
                     slot.focal = focal;
                 } else {
                     b.push(BoxWithLens { label, focal });
                 }
             }
-            '-' => {
+            Operation::Remove => {
                 let b = &mut boxes[box_num];
                 if let Some(slot) = b.iter_mut().position(|it| it.label == label) {
                     b.remove(slot);
                 }
             }
-            _ => panic!("unknown operation"),
         }
     }
 
@@ -67,34 +93,14 @@ pub fn part2(inp: &[String]) -> usize {
     // One plus the box number of the lens in question.
     // The slot number of the lens within the box: 1 for the first lens, 2 for the second lens, and so on.
     // The focal length of the lens.
-    boxes.iter().enumerate().fold(0, |acc, (box_num, it)| {
+    boxes.iter().enumerate().try_fold(0, |acc, (box_num, it)| {
         let mut result = 0;
 
         for (slot, b) in it.iter().enumerate() {
-            result += (box_num + 1) * (slot + 1) * b.focal;
+            let power = checked::product([box_num + 1, slot + 1, b.focal])?;
+            result = checked::add(&result, &power)?;
         }
 
-        acc + result
+        checked::add(&acc, &result)
     })
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const TEST_INPUT: &str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
-
-    #[test]
-    fn test_p1() {
-        let gen = generate(TEST_INPUT);
-        let res = part1(&gen);
-        assert_eq!(res, 1320);
-    }
-
-    #[test]
-    fn test_p2() {
-        let gen = generate(TEST_INPUT);
-        let res = part2(&gen);
-        assert_eq!(res, 145);
-    }
-}
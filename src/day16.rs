@@ -1,4 +1,8 @@
 use aoc_runner_derive::{aoc, aoc_generator};
+use aoc_utils::grid;
+use aoc_utils::parse::Error;
+use aoc_utils::progress::Progress;
+use itertools::Itertools;
 use num::Complex;
 use std::collections::HashSet;
 
@@ -8,12 +12,27 @@ const DOWN: Complex<i64> = Complex::new(0, 1);
 const LEFT: Complex<i64> = Complex::new(-1, 0);
 
 #[derive(PartialEq, Eq, Hash, Clone, Debug)]
-struct Beam {
+pub struct Beam {
     direction: Complex<i64>,
     position: Complex<i64>,
 }
 
 impl Beam {
+    // top left corner, heading right
+    #[must_use]
+    pub const fn start() -> Self {
+        Self {
+            direction: RIGHT,
+            position: Complex::new(0, 0),
+        }
+    }
+
+    // (row, column)
+    #[must_use]
+    pub const fn tile(&self) -> (usize, usize) {
+        (self.position.im as usize, self.position.re as usize)
+    }
+
     fn turn_left(&mut self) {
         self.direction *= -Complex::i();
     }
@@ -24,186 +43,146 @@ impl Beam {
 }
 
 #[aoc_generator(day16)]
-pub fn generate(inp: &str) -> Vec<Vec<char>> {
-    inp.lines().map(|it| it.chars().collect()).collect()
+pub fn generate(inp: &str) -> Result<Vec<Vec<char>>, Error> {
+    grid::parse(inp, ".\\/-|")
 }
 
-fn simulate_beam(
-    beam: &mut Beam,
-    inp_map: &[Vec<char>],
-    result: &mut Vec<Vec<char>>,
-    cache: &mut HashSet<(Complex<i64>, Complex<i64>)>,
-) {
-    loop {
-        if cache.contains(&(beam.position, beam.direction)) {
-            return;
-        }
-
-        result[beam.position.im as usize][beam.position.re as usize] = '#';
-
-        cache.insert((beam.position, beam.direction));
-
-        beam.position += beam.direction;
-
-        if beam.position.im < 0
-            || beam.position.re < 0
-            || beam.position.im as usize >= inp_map.len()
-            || beam.position.re as usize >= inp_map[0].len()
-        {
-            return;
-        }
+// moves `beam` onto the next tile and turns or splits it there;
+// beams leaving the grid are dropped
+fn step_beam(beam: &Beam, inp_map: &[Vec<char>]) -> Vec<Beam> {
+    let mut beam = beam.clone();
+    beam.position += beam.direction;
+
+    if beam.position.im < 0
+        || beam.position.re < 0
+        || beam.position.im as usize >= inp_map.len()
+        || beam.position.re as usize >= inp_map[0].len()
+    {
+        return vec![];
+    }
 
-        let current_tile = inp_map[beam.position.im as usize][beam.position.re as usize];
-        match current_tile {
-            '.' => {}
-            '\\' => {
-                if beam.direction == DOWN || beam.direction == UP {
-                    beam.turn_left();
-                } else {
-                    beam.turn_right();
-                }
+    let current_tile = inp_map[beam.position.im as usize][beam.position.re as usize];
+    match current_tile {
+        '.' => {}
+        '\\' => {
+            if beam.direction == DOWN || beam.direction == UP {
+                beam.turn_left();
+            } else {
+                beam.turn_right();
             }
-            '/' => {
-                if beam.direction == DOWN || beam.direction == UP {
-                    beam.turn_right();
-                } else {
-                    beam.turn_left();
-                }
+        }
+        '/' => {
+            if beam.direction == DOWN || beam.direction == UP {
+                beam.turn_right();
+            } else {
+                beam.turn_left();
             }
-            '-' => {
-                if beam.direction == UP || beam.direction == DOWN {
-                    // split L&R
-                    let mut left_beam = beam.clone();
-                    left_beam.direction = LEFT;
-                    simulate_beam(&mut left_beam, inp_map, result, cache);
-
-                    let mut right_beam = beam.clone();
-                    right_beam.direction = RIGHT;
-                    simulate_beam(&mut right_beam, inp_map, result, cache);
-
-                    return;
-                }
+        }
+        '-' => {
+            if beam.direction == UP || beam.direction == DOWN {
+                // split L&R
+                return [LEFT, RIGHT]
+                    .map(|direction| Beam { direction, ..beam })
+                    .to_vec();
             }
-            '|' => {
-                if beam.direction == LEFT || beam.direction == RIGHT {
-                    // split U&D
-                    let mut up_beam = beam.clone();
-                    up_beam.direction = UP;
-                    simulate_beam(&mut up_beam, inp_map, result, cache);
-
-                    let mut down_beam = beam.clone();
-                    down_beam.direction = DOWN;
-                    simulate_beam(&mut down_beam, inp_map, result, cache);
-
-                    return;
-                }
+        }
+        '|' => {
+            if beam.direction == LEFT || beam.direction == RIGHT {
+                // split U&D
+                return [UP, DOWN]
+                    .map(|direction| Beam { direction, ..beam })
+                    .to_vec();
             }
-            _ => panic!("unknown symbol"),
-        };
-    }
-}
+        }
+        _ => unreachable!("the generator only keeps known tiles"),
+    };
 
-fn count_energized(grid: &[Vec<char>]) -> usize {
-    grid.iter()
-        .fold(0, |acc, it| acc + it.iter().filter(|&c| *c == '#').count())
+    vec![beam]
 }
 
-fn run(beam: &mut Beam, inp: &[Vec<char>]) -> usize {
-    let mut result = vec![vec!['.'; inp[0].len()]; inp.len()];
+// moves every beam of `front` by one tile, dropping beams that retrace a known path
+pub fn advance_beams(front: &[Beam], inp: &[Vec<char>], seen: &mut HashSet<Beam>) -> Vec<Beam> {
+    front
+        .iter()
+        .flat_map(|it| step_beam(it, inp))
+        .filter(|it| seen.insert(it.clone()))
+        .collect()
+}
 
-    let mut cache = HashSet::new();
+fn run(beam: Beam, inp: &[Vec<char>]) -> usize {
+    let mut seen = HashSet::from([beam.clone()]);
 
-    simulate_beam(beam, inp, &mut result, &mut cache);
+    let mut front = vec![beam];
+    while !front.is_empty() {
+        front = advance_beams(&front, inp, &mut seen);
+    }
 
-    count_energized(&result)
+    seen.iter().map(Beam::tile).unique().count()
 }
 
 #[aoc(day16, part1)]
 pub fn part1(inp: &[Vec<char>]) -> usize {
-    let mut beam = Beam {
-        position: Complex::new(0, 0),
-        direction: RIGHT,
-    };
-
-    run(&mut beam, inp)
+    run(Beam::start(), inp)
 }
 
-#[aoc(day16, part2)]
-pub fn part2(inp: &[Vec<char>]) -> usize {
+// reports every entry point tried
+pub fn part2_with<P: Progress + ?Sized>(inp: &[Vec<char>], progress: &P) -> usize {
     let mut result = 0;
 
     let width = i64::try_from(inp[0].len()).expect("in range");
     let height = i64::try_from(inp.len()).expect("in range");
 
+    progress.start(Some(2 * (inp.len() + inp[0].len())));
+
     // top row
     for x in 0..width {
-        let mut beam = Beam {
+        let beam = Beam {
             position: Complex::new(x, 0),
             direction: DOWN,
         };
 
-        result = run(&mut beam, inp).max(result);
+        result = run(beam, inp).max(result);
+        progress.advance(1);
     }
 
     // bottom row
     for x in 0..width {
-        let mut beam = Beam {
+        let beam = Beam {
             position: Complex::new(x, height - 1),
             direction: UP,
         };
 
-        result = run(&mut beam, inp).max(result);
+        result = run(beam, inp).max(result);
+        progress.advance(1);
     }
 
     // left col
     for y in 0..height {
-        let mut beam = Beam {
+        let beam = Beam {
             position: Complex::new(0, y),
             direction: RIGHT,
         };
 
-        result = run(&mut beam, inp).max(result);
+        result = run(beam, inp).max(result);
+        progress.advance(1);
     }
 
     // right col
     for y in 0..height {
-        let mut beam = Beam {
+        let beam = Beam {
             position: Complex::new(width - 1, y),
             direction: LEFT,
         };
 
-        result = run(&mut beam, inp).max(result);
+        result = run(beam, inp).max(result);
+        progress.advance(1);
     }
 
+    progress.finish();
     result
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const TEST_INPUT: &str = ".|...\\....\n\
-                              |.-.\\.....\n\
-                              .....|-...\n\
-                              ........|.\n\
-                              ..........\n\
-                              .........\\\n\
-                              ..../.\\\\..\n\
-                              .-.-/..|..\n\
-                              .|....-|.\\\n\
-                              ..//.|....";
-
-    #[test]
-    fn test_p1() {
-        let gen = generate(TEST_INPUT);
-        let res = part1(&gen);
-        assert_eq!(res, 46);
-    }
-
-    #[test]
-    fn test_p2() {
-        let gen = generate(TEST_INPUT);
-        let res = part2(&gen);
-        assert_eq!(res, 51);
-    }
+#[aoc(day16, part2)]
+pub fn part2(inp: &[Vec<char>]) -> usize {
+    part2_with(inp, &())
 }
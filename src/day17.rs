@@ -1,6 +1,10 @@
 use aoc_runner_derive::{aoc, aoc_generator};
+use aoc_utils::grid;
+use aoc_utils::parse::Error as ParseError;
+use aoc_utils::stats::Stats;
 use itertools::Itertools;
 use num::Complex;
+use std::error::Error;
 
 #[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
 struct Player {
@@ -43,20 +47,25 @@ impl Player {
 }
 
 #[aoc_generator(day17)]
-pub fn generate(inp: &str) -> Vec<Vec<usize>> {
-    inp.lines()
-        .map(|it| {
-            it.chars()
-                .map(|it| it.to_digit(10).expect("digit") as usize)
+pub fn generate(inp: &str) -> Result<Vec<Vec<usize>>, ParseError> {
+    let grid = grid::parse(inp, "0123456789")?;
+
+    Ok(grid
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|it| it.to_digit(10).expect("checked digit") as usize)
                 .collect()
         })
-        .collect()
+        .collect())
 }
 
-fn find_path<F, S>(successors: F, success: S) -> Option<usize>
+// pushes count every successor offered to the queue, whether or not it was cheaper
+fn find_path<F, G, S>(successors: F, success: G, stats: &S) -> Option<usize>
 where
     F: Fn(&Player) -> Vec<(Player, usize)>,
-    S: Fn(&Player) -> bool,
+    G: Fn(&Player) -> bool,
+    S: Stats + ?Sized,
 {
     let player = Player {
         position: Complex::new(0, 0),
@@ -64,6 +73,13 @@ where
         straight: 1,
     };
 
+    let successors = |p: &Player| {
+        let succs = successors(p);
+        stats.add("pops", 1);
+        stats.add("pushes", succs.len());
+        succs
+    };
+
     pathfinding::prelude::dijkstra(&player, successors, success).map(|it| it.1)
 }
 
@@ -90,12 +106,14 @@ const fn on_final_square(p: &Player, width: usize, height: usize) -> bool {
     p.position.im as usize == height - 1 && p.position.re as usize == width - 1
 }
 
-#[aoc(day17, part1)]
-pub fn part1(inp: &[Vec<usize>]) -> usize {
+pub fn part1_with<S: Stats + ?Sized>(
+    inp: &[Vec<usize>],
+    stats: &S,
+) -> Result<usize, Box<dyn Error>> {
     let height = inp.len();
     let width = inp[0].len();
 
-    find_path(
+    let cost = find_path(
         |p| {
             let mut succs = vec![];
 
@@ -110,16 +128,26 @@ pub fn part1(inp: &[Vec<usize>]) -> usize {
             valid_positions(&succs, inp)
         },
         |p| on_final_square(p, width, height),
+        stats,
     )
-    .expect("found path")
+    .ok_or("no path to the bottom right")?;
+
+    Ok(cost)
 }
 
-#[aoc(day17, part2)]
-pub fn part2(inp: &[Vec<usize>]) -> usize {
+#[aoc(day17, part1)]
+pub fn part1(inp: &[Vec<usize>]) -> Result<usize, Box<dyn Error>> {
+    part1_with(inp, &())
+}
+
+pub fn part2_with<S: Stats + ?Sized>(
+    inp: &[Vec<usize>],
+    stats: &S,
+) -> Result<usize, Box<dyn Error>> {
     let height = inp.len();
     let width = inp[0].len();
 
-    find_path(
+    let cost = find_path(
         |p| {
             let mut succs = vec![];
 
@@ -136,52 +164,14 @@ pub fn part2(inp: &[Vec<usize>]) -> usize {
             valid_positions(&succs, inp)
         },
         |p| on_final_square(p, width, height) && p.straight >= 4,
+        stats,
     )
-    .expect("found path")
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const TEST_INPUT: &str = "2413432311323\n\
-                              3215453535623\n\
-                              3255245654254\n\
-                              3446585845452\n\
-                              4546657867536\n\
-                              1438598798454\n\
-                              4457876987766\n\
-                              3637877979653\n\
-                              4654967986887\n\
-                              4564679986453\n\
-                              1224686865563\n\
-                              2546548887735\n\
-                              4322674655533";
-
-    const TEST_INPUT_P2_1: &str = "111111111111\n\
-                                   999999999991\n\
-                                   999999999991\n\
-                                   999999999991\n\
-                                   999999999991";
-
-    #[test]
-    fn test_p1() {
-        let gen = generate(TEST_INPUT);
-        let res = part1(&gen);
-        assert_eq!(res, 102);
-    }
+    .ok_or("no path to the bottom right")?;
 
-    #[test]
-    fn test_p2() {
-        let gen = generate(TEST_INPUT);
-        let res = part2(&gen);
-        assert_eq!(res, 94);
-    }
+    Ok(cost)
+}
 
-    #[test]
-    fn test_p2_2() {
-        let gen = generate(TEST_INPUT_P2_1);
-        let res = part2(&gen);
-        assert_eq!(res, 71);
-    }
+#[aoc(day17, part2)]
+pub fn part2(inp: &[Vec<usize>]) -> Result<usize, Box<dyn Error>> {
+    part2_with(inp, &())
 }
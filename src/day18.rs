@@ -1,5 +1,7 @@
 use aoc_runner_derive::{aoc, aoc_generator};
-use itertools::Itertools;
+use aoc_utils::checked::{self, Overflow};
+use aoc_utils::geometry::Polygon;
+use aoc_utils::parse::{Error, Source};
 use parse_display_derive::{Display, FromStr};
 
 #[derive(Display, FromStr, PartialEq, Eq, Clone, Debug)]
@@ -18,130 +20,72 @@ pub enum Instruction {
 }
 
 #[aoc_generator(day18, part1)]
-pub fn generate_p1(inp: &str) -> Vec<Instruction> {
-    let inp = inp
-        .lines()
-        .map(|it| it.rsplit_once(' ').expect("input").0)
-        .collect_vec();
+pub fn generate_p1(inp: &str) -> Result<Vec<Instruction>, Error> {
+    let source = Source::new(inp);
 
-    inp.iter().map(|it| it.parse().expect("input")).collect()
+    inp.lines()
+        .map(|it| {
+            let (inst, _) = source.split_once(it, " (")?;
+            source.parse(inst)
+        })
+        .collect()
 }
 
 #[aoc_generator(day18, part2)]
-pub fn generate_p2(inp: &str) -> Vec<Instruction> {
-    let inp = inp
-        .lines()
-        .map(|it| it.rsplit_once(' ').expect("input").1)
-        .filter_map(|it| it.strip_prefix("(#").and_then(|s| s.strip_suffix(')')))
-        .collect_vec();
-
-    inp.iter()
+pub fn generate_p2(inp: &str) -> Result<Vec<Instruction>, Error> {
+    let source = Source::new(inp);
+
+    inp.lines()
         .map(|it| {
-            let (num, dir) = it.split_at(it.len() - 1);
-            let num = i64::from_str_radix(num, 16).expect("hex number");
+            let (_, colour) = source.split_once(it, " (#")?;
+            let colour = colour
+                .strip_suffix(')')
+                .filter(|it| it.len() == 6 && it.is_ascii())
+                .ok_or_else(|| source.error(colour, "expected six hex digits and `)`"))?;
 
             // 0 means R, 1 means D, 2 means L, and 3 means U.
-            let dgt = dir.parse::<i64>().expect("single digit");
-
-            if dgt == 0 {
-                Instruction::Right(num)
-            } else if dgt == 1 {
-                Instruction::Down(num)
-            } else if dgt == 2 {
-                Instruction::Left(num)
-            } else {
-                Instruction::Up(num)
+            let (num, dir) = colour.split_at(5);
+            let num = i64::from_str_radix(num, 16).map_err(|e| source.error(num, e))?;
+
+            match dir {
+                "0" => Ok(Instruction::Right(num)),
+                "1" => Ok(Instruction::Down(num)),
+                "2" => Ok(Instruction::Left(num)),
+                "3" => Ok(Instruction::Up(num)),
+                _ => Err(source.error(dir, "unknown direction")),
             }
         })
         .collect()
 }
 
-fn fill_map(inp: &[Instruction]) -> (Vec<(i64, i64)>, usize) {
+fn fill_map(inp: &[Instruction]) -> Result<Polygon, Overflow> {
     let mut map = Vec::new();
 
-    let mut perim = 0;
-
     let mut cur = (0, 0);
     map.push(cur);
 
     for inst in inp {
         match *inst {
-            Instruction::Up(n) => {
-                cur.0 += n;
-                map.push(cur);
-                perim += n;
-            }
-            Instruction::Right(n) => {
-                cur.1 += n;
-                map.push(cur);
-                perim += n;
-            }
-            Instruction::Down(n) => {
-                cur.0 -= n;
-                map.push(cur);
-                perim += n;
-            }
-            Instruction::Left(n) => {
-                cur.1 -= n;
-                map.push(cur);
-                perim += n;
-            }
+            Instruction::Up(n) => cur.1 = checked::add(&cur.1, &n)?,
+            Instruction::Right(n) => cur.0 = checked::add(&cur.0, &n)?,
+            Instruction::Down(n) => cur.1 = checked::sub(&cur.1, &n)?,
+            Instruction::Left(n) => cur.0 = checked::sub(&cur.0, &n)?,
         };
+
+        map.push(cur);
     }
 
-    (map, perim as usize)
+    Ok(Polygon::new(map))
 }
 
 #[aoc(day18, part1)]
-pub fn part1(inp: &[Instruction]) -> usize {
-    let (map, perim) = fill_map(inp);
-
-    let mut area = 0;
-    for i in 0..map.len() {
-        let (y, x) = map[i];
-        let (yn, xn) = map[(i + 1) % map.len()];
+pub fn part1(inp: &[Instruction]) -> Result<usize, Overflow> {
+    let polygon = fill_map(inp)?;
 
-        area += (x + xn) * (y - yn);
-    }
-
-    1 + (area.unsigned_abs() as usize + perim) / 2
+    Ok(checked::add(&polygon.interior_points()?, &polygon.boundary_points()?)? as usize)
 }
 
 #[aoc(day18, part2)]
-pub fn part2(inp: &[Instruction]) -> usize {
+pub fn part2(inp: &[Instruction]) -> Result<usize, Overflow> {
     part1(inp)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const TEST_INPUT: &str = "R 6 (#70c710)\n\
-                              D 5 (#0dc571)\n\
-                              L 2 (#5713f0)\n\
-                              D 2 (#d2c081)\n\
-                              R 2 (#59c680)\n\
-                              D 2 (#411b91)\n\
-                              L 5 (#8ceee2)\n\
-                              U 2 (#caa173)\n\
-                              L 1 (#1b58a2)\n\
-                              U 2 (#caa171)\n\
-                              R 2 (#7807d2)\n\
-                              U 3 (#a77fa3)\n\
-                              L 2 (#015232)\n\
-                              U 2 (#7a21e3)";
-
-    #[test]
-    fn test_p1() {
-        let gen = generate_p1(TEST_INPUT);
-        let res = part1(&gen);
-        assert_eq!(res, 62);
-    }
-
-    #[test]
-    fn test_p2() {
-        let gen = generate_p2(TEST_INPUT);
-        let res = part2(&gen);
-        assert_eq!(res, 952_408_144_115);
-    }
-}
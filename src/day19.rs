@@ -1,7 +1,9 @@
 use aoc_runner_derive::{aoc, aoc_generator};
+use aoc_utils::checked::{self, Overflow};
+use aoc_utils::interval::{Interval, IntervalBox};
+use aoc_utils::parse::{Error, Source};
 use itertools::Itertools;
 use parse_display_derive::{Display, FromStr};
-use std::ops::RangeInclusive;
 
 #[derive(Display, FromStr, PartialEq, Eq, Copy, Clone, Debug)]
 pub enum InputValue {
@@ -45,75 +47,34 @@ pub struct Input {
     s: usize,
 }
 
-#[derive(PartialEq, Eq, Clone, Debug)]
-pub struct InputRange {
-    x: RangeInclusive<usize>,
-    m: RangeInclusive<usize>,
-    a: RangeInclusive<usize>,
-    s: RangeInclusive<usize>,
-}
-
-impl InputRange {
-    fn count(&self) -> usize {
-        self.x.clone().count()
-            * self.m.clone().count()
-            * self.a.clone().count()
-            * self.s.clone().count()
-    }
-
-    #[allow(clippy::range_minus_one)]
-    fn sub_range_less(&self, inp: InputValue, n: usize) -> Self {
-        match inp {
-            InputValue::X => Self {
-                x: *self.x.start()..=n - 1,
-                ..self.clone()
-            },
-            InputValue::M => Self {
-                m: *self.m.start()..=n - 1,
-                ..self.clone()
-            },
-            InputValue::A => Self {
-                a: *self.a.start()..=n - 1,
-                ..self.clone()
-            },
-            InputValue::S => Self {
-                s: *self.s.start()..=n - 1,
-                ..self.clone()
-            },
+impl InputValue {
+    const fn dim(self) -> usize {
+        match self {
+            Self::X => 0,
+            Self::M => 1,
+            Self::A => 2,
+            Self::S => 3,
         }
     }
+}
 
-    fn sub_range_greater(&self, inp: InputValue, n: usize) -> Self {
-        match inp {
-            InputValue::X => Self {
-                x: n + 1..=*self.x.end(),
-                ..self.clone()
-            },
-            InputValue::M => Self {
-                m: n + 1..=*self.m.end(),
-                ..self.clone()
-            },
-            InputValue::A => Self {
-                a: n + 1..=*self.a.end(),
-                ..self.clone()
-            },
-            InputValue::S => Self {
-                s: n + 1..=*self.s.end(),
-                ..self.clone()
-            },
-        }
-    }
+// one dimension per `InputValue`
+type InputRange = IntervalBox<4>;
+
+// the ranges only go up to 4000, so larger bounds all cut in the same place
+fn as_i64(n: usize) -> i64 {
+    i64::try_from(n).unwrap_or(i64::MAX)
 }
 
 fn find_workflow<'w>(name: &str, wfs: &'w [Workflow]) -> &'w Workflow {
     wfs.iter()
         .find(|it| it.id == name)
-        .expect("existing workflow")
+        .expect("the generator checked every workflow exists")
 }
 
 impl Input {
-    const fn rating(&self) -> usize {
-        self.x + self.m + self.a + self.s
+    fn rating(&self) -> Result<usize, Overflow> {
+        checked::sum([self.x, self.m, self.a, self.s])
     }
 
     const fn take_value(&self, iv: InputValue) -> usize {
@@ -165,145 +126,159 @@ pub struct ParsedInput {
     inputs: Vec<Input>,
 }
 
-fn parse_rules(s: &str) -> Vec<Rule> {
-    s.split(',').fold(vec![], |mut acc, rule| {
-        let r = rule.parse::<Rule>().expect("rule");
-        acc.push(r);
-        acc
+fn parse_workflow(source: &Source<'_>, line: &str) -> Result<Workflow, Error> {
+    let (id, rest) = source.split_once(line, "{")?;
+    let rest = rest
+        .strip_suffix('}')
+        .ok_or_else(|| source.error(line, "expected a closing `}`"))?;
+
+    let rules = rest
+        .split(',')
+        .map(|rule| source.parse::<Rule>(rule))
+        .collect::<Result<_, _>>()?;
+
+    Ok(Workflow {
+        id: id.to_string(),
+        rules,
     })
 }
 
-fn parse_workflows(s: &str) -> Vec<Workflow> {
-    s.lines().fold(vec![], |mut acc, line| {
-        let (id, mut rest) = line.split_once('{').expect("opening {");
-        rest = rest.strip_suffix('}').expect("closing }");
+const fn target(rule: &Rule) -> &String {
+    match rule {
+        Rule::LT(_, _, state) | Rule::GT(_, _, state) | Rule::Next(state) => state,
+    }
+}
 
-        let rules = parse_rules(rest);
+// the solvers follow workflows recursively, so every target has to exist and no chain may loop
+fn check_workflows(source: &Source<'_>, lines: &[&str], wfs: &[Workflow]) -> Result<(), Error> {
+    if !wfs.iter().any(|it| it.id == "in") {
+        return Err(source.error(source.text(), "no workflow `in`"));
+    }
 
-        acc.push(Workflow {
-            id: id.to_string(),
-            rules,
-        });
+    let index = |state: &str| wfs.iter().position(|it| it.id == state);
+    let mut successors = vec![];
+    for (line, wf) in lines.iter().zip(wfs) {
+        let mut next = vec![];
+        for state in wf.rules.iter().map(target) {
+            if state == "A" || state == "R" {
+                continue;
+            }
+            next.push(
+                index(state).ok_or_else(|| {
+                    source.error(line, format!("workflow {state} is never defined"))
+                })?,
+            );
+        }
+        successors.push(next);
+    }
 
-        acc
-    })
+    // 0 unvisited, 1 on the current path, 2 done
+    let mut state = vec![0u8; wfs.len()];
+    for start in 0..wfs.len() {
+        let mut stack = vec![(start, 0)];
+        while let Some((node, child)) = stack.pop() {
+            if child == 0 {
+                if state[node] == 2 {
+                    continue;
+                }
+                state[node] = 1;
+            }
+            if let Some(&next) = successors[node].get(child) {
+                stack.push((node, child + 1));
+                match state[next] {
+                    0 => stack.push((next, 0)),
+                    1 => return Err(source.error(lines[next], "workflows loop")),
+                    _ => {}
+                }
+            } else {
+                state[node] = 2;
+            }
+        }
+    }
+
+    Ok(())
 }
 
 #[aoc_generator(day19)]
-pub fn generate(inp: &str) -> ParsedInput {
-    let (rules, values) = inp.split_once("\n\n").expect("input");
+pub fn generate(inp: &str) -> Result<ParsedInput, Error> {
+    let source = Source::new(inp);
+    let (rules, values) = source.split_once(inp, "\n\n")?;
 
-    let workflows = parse_workflows(rules);
+    let lines = rules.lines().collect_vec();
+    let workflows = lines
+        .iter()
+        .map(|line| parse_workflow(&source, line))
+        .collect::<Result<Vec<_>, _>>()?;
+    check_workflows(&source, &lines, &workflows)?;
 
     let inputs = values
         .lines()
-        .map(|it| it.parse().expect("input"))
-        .collect_vec();
+        .map(|it| source.parse(it))
+        .collect::<Result<_, _>>()?;
 
-    ParsedInput { workflows, inputs }
+    Ok(ParsedInput { workflows, inputs })
 }
 
 #[aoc(day19, part1)]
-pub fn part1(inp: &ParsedInput) -> usize {
-    inp.inputs.iter().fold(0, |acc, it| {
-        acc + if it.is_accepted(&inp.workflows) {
-            it.rating()
-        } else {
-            0
-        }
-    })
+pub fn part1(inp: &ParsedInput) -> Result<usize, Overflow> {
+    inp.inputs
+        .iter()
+        .filter(|it| it.is_accepted(&inp.workflows))
+        .try_fold(0, |acc, it| checked::add(&acc, &it.rating()?))
 }
 
-#[allow(clippy::range_minus_one)]
-fn count_combinations(mut range: InputRange, cur_wf: &Workflow, wfs: &[Workflow]) -> usize {
+fn count_combinations(
+    mut range: InputRange,
+    cur_wf: &Workflow,
+    wfs: &[Workflow],
+) -> Result<u64, Overflow> {
     let mut result = 0;
 
-    let count_sub_range = |sub_range: InputRange, state: &str| -> usize {
-        if sub_range.count() > 0 {
+    let count_sub_range = |sub_range: InputRange, state: &str| -> Result<u64, Overflow> {
+        if !sub_range.is_empty() {
             if state == "A" {
-                return sub_range.count();
+                return sub_range.volume();
             } else if state != "R" {
                 let next_wf = find_workflow(state, wfs);
                 return count_combinations(sub_range, next_wf, wfs);
             }
         }
 
-        0
+        Ok(0)
     };
 
     for r in &cur_wf.rules {
         match r {
             Rule::LT(v, n, state) => {
-                let sub_range = range.sub_range_less(*v, *n);
-                result += count_sub_range(sub_range, state);
-                range = range.sub_range_greater(*v, *n - 1);
+                let (sub_range, rest) = range.split_at(v.dim(), as_i64(*n));
+                result = checked::add(&result, &count_sub_range(sub_range, state)?)?;
+                range = rest;
             }
             Rule::GT(v, n, state) => {
-                let sub_range = range.sub_range_greater(*v, *n);
-                result += count_sub_range(sub_range, state);
-                range = range.sub_range_less(*v, *n + 1);
+                let (rest, sub_range) = range.split_at(v.dim(), as_i64(*n).saturating_add(1));
+                result = checked::add(&result, &count_sub_range(sub_range, state)?)?;
+                range = rest;
             }
             Rule::Next(state) => {
                 if state == "R" {
-                    return result;
+                    return Ok(result);
                 } else if state == "A" {
-                    return result + range.count();
+                    return checked::add(&result, &range.volume()?);
                 }
 
                 let next_wf = find_workflow(state, wfs);
-                result += count_combinations(range.clone(), next_wf, wfs);
+                result = checked::add(&result, &count_combinations(range, next_wf, wfs)?)?;
             }
         };
     }
 
-    result
+    Ok(result)
 }
 
 #[aoc(day19, part2)]
-pub fn part2(inp: &ParsedInput) -> usize {
-    let input_range = InputRange {
-        x: 1..=4000,
-        m: 1..=4000,
-        a: 1..=4000,
-        s: 1..=4000,
-    };
+pub fn part2(inp: &ParsedInput) -> Result<u64, Overflow> {
+    let input_range = InputRange::new([Interval::inclusive(1, 4000); 4]);
 
     let cur_wf = find_workflow("in", &inp.workflows);
     count_combinations(input_range, cur_wf, &inp.workflows)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const TEST_INPUT: &str = "px{a<2006:qkq,m>2090:A,rfg}\n\
-                              pv{a>1716:R,A}\n\
-                              lnx{m>1548:A,A}\n\
-                              rfg{s<537:gd,x>2440:R,A}\n\
-                              qs{s>3448:A,lnx}\n\
-                              qkq{x<1416:A,crn}\n\
-                              crn{x>2662:A,R}\n\
-                              in{s<1351:px,qqz}\n\
-                              qqz{s>2770:qs,m<1801:hdj,R}\n\
-                              gd{a>3333:R,R}\n\
-                              hdj{m>838:A,pv}\n\
-                              \n\
-                              {x=787,m=2655,a=1222,s=2876}\n\
-                              {x=1679,m=44,a=2067,s=496}\n\
-                              {x=2036,m=264,a=79,s=2244}\n\
-                              {x=2461,m=1339,a=466,s=291}\n\
-                              {x=2127,m=1623,a=2188,s=1013}";
-    #[test]
-    fn test_p1() {
-        let gen = generate(TEST_INPUT);
-        let res = part1(&gen);
-        assert_eq!(res, 19114);
-    }
-
-    #[test]
-    fn test_p2() {
-        let gen = generate(TEST_INPUT);
-        let res = part2(&gen);
-        assert_eq!(res, 167_409_079_868_000);
-    }
-}
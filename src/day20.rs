@@ -1,14 +1,23 @@
+use crate::cache;
 use aoc_runner_derive::{aoc, aoc_generator};
+use aoc_utils::cancel::{Interrupted, Token};
+use aoc_utils::checked::{self, Overflow};
+use aoc_utils::graph::{Graph, NodeId};
+use aoc_utils::parse::{Error as ParseError, Source};
+use aoc_utils::stats::Stats;
 use itertools::Itertools;
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::fmt;
 
-#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Copy, Clone, Debug)]
 pub enum Signal {
     Low,
     High,
 }
 
-#[derive(PartialEq, Eq, Clone, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug)]
 pub enum ModuleKind {
     // High: nothing
     // Low:
@@ -19,128 +28,256 @@ pub enum ModuleKind {
     // Remember all inputs (default low)
     // all high -> low
     // else     -> high
-    Conjunction(HashMap<usize, Signal>),
+    Conjunction(HashMap<NodeId, Signal>),
 
     Broadcast,
+
+    // Only ever receives pulses (e.g. `output` or `rx`)
+    Sink,
+}
+
+// `modules` is indexed by the module's id in `graph`
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Network {
+    graph: Graph,
+    modules: Vec<ModuleKind>,
 }
 
-#[derive(PartialEq, Eq, Clone, Debug)]
-pub struct Module {
-    id: String,
-    kind: ModuleKind,
-    dest_mods: Vec<String>,
+impl Network {
+    // returns the number of (high, low) pulses sent, including the button's
+    pub fn press_button(&mut self) -> (usize, usize) {
+        let _span = tracing::trace_span!("press").entered();
+        let broadcaster = self
+            .graph
+            .id("broadcaster")
+            .expect("the generator checked there is a broadcaster");
+
+        let mut high = 0;
+        let mut low = 1;
+
+        let mut queue = VecDeque::from([(broadcaster, Signal::Low, None)]);
+        while let Some((idx, signal, from)) = queue.pop_front() {
+            let (h, l) = handle_signal(
+                &self.graph,
+                &mut self.modules,
+                &mut queue,
+                idx,
+                signal,
+                from,
+            );
+            high += h;
+            low += l;
+        }
+
+        (high, low)
+    }
 }
 
-fn parse_module(line: &str) -> Module {
-    let (module, dests) = line.split_once(" -> ").expect("input");
+// one module per line, along with its current state
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for id in self.graph.nodes() {
+            let dests = self
+                .graph
+                .successors(id)
+                .iter()
+                .map(|&it| self.graph.label(it))
+                .join(", ");
+
+            let label = self.graph.label(id);
+            match &self.modules[id as usize] {
+                ModuleKind::FlipFlop(on) => {
+                    let state = if *on { "on" } else { "off" };
+                    writeln!(f, "%{label} [{state}] -> {dests}")?;
+                }
+                ModuleKind::Conjunction(map) => {
+                    let memory = map
+                        .iter()
+                        .sorted_by_key(|(&input, _)| input)
+                        .map(|(&input, signal)| format!("{}={signal:?}", self.graph.label(input)))
+                        .join(" ");
+                    writeln!(f, "&{label} [{memory}] -> {dests}")?;
+                }
+                ModuleKind::Broadcast => writeln!(f, "{label} -> {dests}")?,
+                ModuleKind::Sink => writeln!(f, "{label}")?,
+            }
+        }
+
+        Ok(())
+    }
+}
 
-    let kind = if module.starts_with('&') {
-        ModuleKind::Conjunction(HashMap::new())
-    } else if module.starts_with('%') {
-        ModuleKind::FlipFlop(false)
+fn parse_module(
+    source: &Source<'_>,
+    line: &str,
+    graph: &mut Graph,
+) -> Result<(NodeId, ModuleKind), ParseError> {
+    let (module, dests) = source.split_once(line, " -> ")?;
+
+    let (kind, name) = if let Some(name) = module.strip_prefix('&') {
+        (ModuleKind::Conjunction(HashMap::new()), name)
+    } else if let Some(name) = module.strip_prefix('%') {
+        (ModuleKind::FlipFlop(false), name)
+    } else if module == "broadcaster" {
+        (ModuleKind::Broadcast, module)
     } else {
-        ModuleKind::Broadcast
+        return Err(source.error(module, "unknown module type"));
     };
 
-    Module {
-        id: module.trim_start_matches(['&', '%']).to_string(),
-        kind,
-        dest_mods: dests.split(", ").map(ToString::to_string).collect(),
+    let id = graph.add_node(name).map_err(|e| source.error(name, e))?;
+    for dest in dests.split(", ") {
+        if dest.is_empty() || dest == "broadcaster" {
+            return Err(source.error(dest, "invalid destination"));
+        }
+        let dest = graph.add_node(dest).map_err(|e| source.error(dest, e))?;
+        graph.add_edge(id, dest);
     }
+
+    Ok((id, kind))
 }
 
-#[aoc_generator(day20)]
-pub fn generate(inp: &str) -> Vec<Module> {
-    let mut parsed = inp.lines().map(parse_module).collect_vec();
-
-    for idx in 0..parsed.len() {
-        let cur_name = &parsed[idx].id;
-        let all_in = parsed
-            .iter()
-            .enumerate()
-            .filter_map(|(index, it)| it.dest_mods.contains(cur_name).then_some(index))
-            .collect_vec();
-
-        if let ModuleKind::Conjunction(map) = &mut parsed[idx].kind {
+fn parse(inp: &str) -> Result<Network, ParseError> {
+    let source = Source::new(inp);
+    let mut graph = Graph::new();
+    let mut parsed = vec![];
+    for line in inp.lines() {
+        let (id, kind) = parse_module(&source, line, &mut graph)?;
+        if parsed.iter().any(|&(it, _)| it == id) {
+            return Err(source.error(line, "module defined twice"));
+        }
+        parsed.push((id, kind));
+    }
+
+    if graph.id("broadcaster").is_none() {
+        return Err(source.error(inp, "no broadcaster"));
+    }
+
+    let mut modules = vec![ModuleKind::Sink; graph.len()];
+    for (id, kind) in parsed {
+        modules[id as usize] = kind;
+    }
+
+    for id in graph.nodes() {
+        if let ModuleKind::Conjunction(map) = &mut modules[id as usize] {
             // [...] they initially default to remembering a low pulse for each input
-            for idx in all_in {
-                map.insert(idx, Signal::Low);
+            for &input in graph.predecessors(id) {
+                map.insert(input, Signal::Low);
             }
         }
     }
 
-    parsed
+    Ok(Network { graph, modules })
 }
 
-#[aoc(day20, part1)]
-pub fn part1(inp: &[Module]) -> usize {
+#[aoc_generator(day20)]
+pub fn generate(inp: &str) -> Result<Network, ParseError> {
+    cache::try_cached(20, "generate", inp, parse)
+}
+
+fn record_press<S: Stats + ?Sized>(stats: &S, pulses: usize) {
+    stats.add("presses", 1);
+    stats.add("pulses", pulses);
+    stats.max("max_pulses_per_press", pulses);
+}
+
+pub fn part1_with<S: Stats + ?Sized>(inp: &Network, stats: &S) -> Result<usize, Overflow> {
     let mut high = 0;
     let mut low = 0;
 
-    let mut inp = inp.to_owned();
-    let broadcaster = find_module("broadcaster", &inp).expect("start node");
+    let mut network = inp.clone();
     for _ in 0..1000 {
-        let mut queue = VecDeque::from([(broadcaster, Signal::Low, None)]);
-        low += 1;
-
-        while let Some((idx, signal, from)) = queue.pop_front() {
-            let (h, l) = handle_signal(&mut inp, &mut queue, idx, signal, from);
-            high += h;
-            low += l;
-        }
+        let (h, l) = network.press_button();
+        record_press(stats, h + l);
+        high = checked::add(&high, &h)?;
+        low = checked::add(&low, &l)?;
     }
 
-    high * low
+    checked::mul(&high, &low)
 }
 
-#[aoc(day20, part2)]
-pub fn part2(inp: &[Module]) -> usize {
-    let mut inp = inp.to_owned();
+#[aoc(day20, part1)]
+pub fn part1(inp: &Network) -> Result<usize, Overflow> {
+    part1_with(inp, &())
+}
+
+// fails with either `Interrupted<usize>` or `Overflow`
+pub fn part2_with<S: Stats + ?Sized>(
+    inp: &Network,
+    token: &Token,
+    stats: &S,
+) -> Result<usize, Box<dyn Error>> {
+    let mut modules = inp.modules.clone();
 
     // rx's sole input is the conjunction mf
     // mf's inputs are: jf, sh, bh, mz
     // -> check when they emit true (cycling)
-    ["jf", "sh", "bh", "mz"]
+    let mut result = 1usize;
+    for idx in ["jf", "sh", "bh", "mz"]
         .iter()
-        .filter_map(|it| find_module(it, &inp).map(|idx| find_cycle_for(idx, &mut inp)))
-        .product()
+        .filter_map(|it| inp.graph.id(it))
+    {
+        let period = find_cycle_for(idx, &inp.graph, &mut modules, token, stats)?;
+        result = checked::mul(&result, &period)?;
+    }
+
+    Ok(result)
 }
 
-fn find_module(id: &str, modules: &[Module]) -> Option<usize> {
-    modules.iter().position(|it| it.id == id)
+#[aoc(day20, part2)]
+pub fn part2(inp: &Network) -> Result<usize, Box<dyn Error>> {
+    part2_with(inp, &Token::new(), &())
 }
 
-fn find_cycle_for(node: usize, inp: &mut [Module]) -> usize {
-    let broadcaster = find_module("broadcaster", inp).expect("start node");
+// reports the number of button presses when interrupted
+fn find_cycle_for<S: Stats + ?Sized>(
+    node: NodeId,
+    graph: &Graph,
+    modules: &mut [ModuleKind],
+    token: &Token,
+    stats: &S,
+) -> Result<usize, Interrupted<usize>> {
+    let broadcaster = graph
+        .id("broadcaster")
+        .expect("the generator checked there is a broadcaster");
 
     let mut prev_cycle = 0;
 
     for num_presses in 0.. {
+        token.check_at(num_presses)?;
+        let _span = tracing::trace_span!("press", num_presses).entered();
+
         let mut queue = VecDeque::from([(broadcaster, Signal::Low, None)]);
+        let mut pulses = 1;
 
         while let Some((idx, signal, from)) = queue.pop_front() {
             if signal == Signal::High && from == Some(node) {
                 if prev_cycle == 0 {
                     prev_cycle = num_presses;
                 } else {
-                    return num_presses - prev_cycle;
+                    let period = num_presses - prev_cycle;
+                    tracing::debug!(node = graph.label(node), period, "found cycle");
+                    record_press(stats, pulses);
+                    return Ok(period);
                 }
             }
 
-            handle_signal(inp, &mut queue, idx, signal, from);
+            let (high, low) = handle_signal(graph, modules, &mut queue, idx, signal, from);
+            pulses += high + low;
         }
+
+        record_press(stats, pulses);
     }
 
     unreachable!("cycle exists")
 }
 
 fn send_pulse(
-    cur: usize,
+    cur: NodeId,
     signal: Signal,
-    inp: &[Module],
-    queue: &mut VecDeque<(usize, Signal, Option<usize>)>,
+    graph: &Graph,
+    queue: &mut VecDeque<(NodeId, Signal, Option<NodeId>)>,
 ) -> (usize, usize) {
-    let dest_mods = &inp[cur].dest_mods;
+    let dest_mods = graph.successors(cur);
     let high = if signal == Signal::High {
         dest_mods.len()
     } else {
@@ -149,24 +286,23 @@ fn send_pulse(
 
     let low = dest_mods.len() - high;
 
-    for dn in dest_mods {
-        if let Some(i) = find_module(dn, inp) {
-            queue.push_back((i, signal, Some(cur)));
-        }
+    for &dn in dest_mods {
+        queue.push_back((dn, signal, Some(cur)));
     }
 
     (high, low)
 }
 
 fn handle_signal(
-    inp: &mut [Module],
-    queue: &mut VecDeque<(usize, Signal, Option<usize>)>,
-    idx: usize,
+    graph: &Graph,
+    modules: &mut [ModuleKind],
+    queue: &mut VecDeque<(NodeId, Signal, Option<NodeId>)>,
+    idx: NodeId,
     signal: Signal,
-    from: Option<usize>,
+    from: Option<NodeId>,
 ) -> (usize, usize) {
-    match inp[idx].kind {
-        ModuleKind::Broadcast => send_pulse(idx, signal, inp, queue),
+    match modules[idx as usize] {
+        ModuleKind::Broadcast => send_pulse(idx, signal, graph, queue),
         ModuleKind::FlipFlop(ref mut state) => {
             if signal == Signal::Low {
                 let to_send = if *state {
@@ -179,7 +315,7 @@ fn handle_signal(
 
                 *state = !*state;
 
-                send_pulse(idx, to_send, inp, queue)
+                send_pulse(idx, to_send, graph, queue)
             } else {
                 (0, 0)
             }
@@ -197,37 +333,8 @@ fn handle_signal(
                 Signal::High
             };
 
-            send_pulse(idx, to_send, inp, queue)
+            send_pulse(idx, to_send, graph, queue)
         }
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const TEST_INPUT: &str = "broadcaster -> a, b, c\n\
-                              %a -> b\n\
-                              %b -> c\n\
-                              %c -> inv\n\
-                              &inv -> a";
-
-    const TEST_INPUT_2: &str = "broadcaster -> a\n\
-                                %a -> inv, con\n\
-                                &inv -> b\n\
-                                %b -> con\n\
-                                &con -> output";
-    #[test]
-    fn test_p1() {
-        let gen = generate(TEST_INPUT);
-        let res = part1(&gen);
-        assert_eq!(res, 32_000_000);
-    }
-
-    #[test]
-    fn test_p1_2() {
-        let gen = generate(TEST_INPUT_2);
-        let res = part1(&gen);
-        assert_eq!(res, 11_687_500);
+        ModuleKind::Sink => (0, 0),
     }
 }
@@ -1,70 +1,48 @@
 use aoc_runner_derive::{aoc, aoc_generator};
-use itertools::{iproduct, Itertools};
+use aoc_utils::checked::{self, Overflow};
+use aoc_utils::grid;
+use aoc_utils::parse::{Error as ParseError, Source};
+use itertools::Itertools;
 use num::Integer;
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
 
 #[aoc_generator(day21)]
-pub fn generate(inp: &str) -> Vec<Vec<char>> {
-    inp.lines().map(|it| it.chars().collect()).collect()
-}
+pub fn generate(inp: &str) -> Result<Vec<Vec<char>>, ParseError> {
+    let grid = grid::parse(inp, ".#S")?;
+    if inp.matches('S').count() != 1 {
+        return Err(Source::new(inp).error(inp, "expected a single start tile"));
+    }
 
-fn count_reachable(num_steps: usize, inp: &[Vec<char>]) -> usize {
-    let mut start_pos = (0, 0);
+    Ok(grid)
+}
 
-    for (y, x) in iproduct!(0..inp.len(), 0..inp[0].len()) {
-        if inp[y][x] == 'S' {
-            start_pos = (y, x);
-            break;
-        }
-    }
+pub fn count_reachable(num_steps: usize, inp: &[Vec<char>]) -> usize {
+    let start_pos = grid::find(inp, &'S').expect("the generator checked there is a start");
 
     let mut inp = inp.to_owned();
     inp[start_pos.0][start_pos.1] = '.';
 
-    pathfinding::prelude::dfs_reach((start_pos, 0), |&((y, x), steps)| {
-        let mut succs = vec![];
-
+    let size = (inp.len(), inp[0].len());
+    pathfinding::prelude::dfs_reach((start_pos, 0), |&(pos, steps)| {
         if steps > num_steps {
-            return succs;
-        }
-
-        // top
-        if y > 0 && inp[y - 1][x] == '.' {
-            succs.push(((y - 1, x), steps + 1));
-        }
-
-        // right
-        if x < inp[y].len() - 1 && inp[y][x + 1] == '.' {
-            succs.push(((y, x + 1), steps + 1));
-        }
-
-        // bottom
-        if y < inp.len() - 1 && inp[y + 1][x] == '.' {
-            succs.push(((y + 1, x), steps + 1));
-        }
-
-        // left
-        if x > 0 && inp[y][x - 1] == '.' {
-            succs.push(((y, x - 1), steps + 1));
+            return vec![];
         }
 
-        succs
+        grid::neighbours(pos, size)
+            .filter(|&(y, x)| inp[y][x] == '.')
+            .map(|it| (it, steps + 1))
+            .collect()
     })
     .filter(|(_, steps)| *steps <= num_steps && steps.is_even())
     .unique_by(|(p, _)| *p)
     .count()
 }
 
-#[allow(dead_code)]
 #[allow(clippy::cast_possible_wrap)]
-fn count_reachable_infinite(num_steps: usize, inp: &[Vec<char>]) -> usize {
-    let mut start_pos = (0, 0);
-
-    for (y, x) in iproduct!(0..inp.len(), 0..inp[0].len()) {
-        if inp[y][x] == 'S' {
-            start_pos = (y as i64, x as i64);
-            break;
-        }
-    }
+pub fn count_reachable_infinite(num_steps: usize, inp: &[Vec<char>]) -> usize {
+    let (y, x) = grid::find(inp, &'S').expect("the generator checked there is a start");
+    let start_pos = (y as i64, x as i64);
 
     let mut inp = inp.to_owned();
     inp[start_pos.0 as usize][start_pos.1 as usize] = '.';
@@ -72,54 +50,29 @@ fn count_reachable_infinite(num_steps: usize, inp: &[Vec<char>]) -> usize {
     let height = inp.len() as i64;
     let width = inp[0].len() as i64;
 
-    pathfinding::prelude::dfs_reach((start_pos, 0), |&((y, x), steps)| {
-        let mut succs = vec![];
-
-        if steps > num_steps {
-            return succs;
-        }
-
-        // top
-        {
-            let yy = (y - 1).rem_euclid(height) as usize;
-            let xx = x.rem_euclid(width) as usize;
-            if inp[yy][xx] == '.' {
-                succs.push(((y - 1, x), steps + 1));
-            }
-        }
+    // a plot is reachable in exactly `num_steps` if it can be reached in fewer
+    // steps of the same parity (by stepping back and forth)
+    let mut distances = HashMap::from([(start_pos, 0)]);
+    let mut queue = VecDeque::from([(start_pos, 0)]);
 
-        // right
-        {
-            let yy = y.rem_euclid(height) as usize;
-            let xx = (x + 1).rem_euclid(width) as usize;
-            if inp[yy][xx] == '.' {
-                succs.push(((y, x + 1), steps + 1));
-            }
-        }
-
-        // bottom
-        {
-            let yy = (y + 1).rem_euclid(height) as usize;
-            let xx = x.rem_euclid(width) as usize;
-            if inp[yy][xx] == '.' {
-                succs.push(((y + 1, x), steps + 1));
-            }
+    while let Some(((y, x), steps)) = queue.pop_front() {
+        if steps == num_steps {
+            continue;
         }
 
-        // left
-        {
-            let yy = y.rem_euclid(height) as usize;
-            let xx = (x - 1).rem_euclid(width) as usize;
-            if inp[yy][xx] == '.' {
-                succs.push(((y, x - 1), steps + 1));
+        for (ny, nx) in [(y - 1, x), (y, x + 1), (y + 1, x), (y, x - 1)] {
+            let tile = inp[ny.rem_euclid(height) as usize][nx.rem_euclid(width) as usize];
+            if tile == '.' && !distances.contains_key(&(ny, nx)) {
+                distances.insert((ny, nx), steps + 1);
+                queue.push_back(((ny, nx), steps + 1));
             }
         }
+    }
 
-        succs
-    })
-    .filter(|(_, steps)| *steps <= num_steps && steps % 2 == num_steps % 2)
-    .unique_by(|&(p, _)| p)
-    .count()
+    distances
+        .values()
+        .filter(|&&steps| steps % 2 == num_steps % 2)
+        .count()
 }
 
 #[aoc(day21, part1)]
@@ -128,61 +81,36 @@ pub fn part1(inp: &[Vec<char>]) -> usize {
 }
 
 #[aoc(day21, part2)]
-pub const fn part2(inp: &[Vec<char>]) -> usize {
-    // let start_pos = (65, 65);
-    // let x0 = count_reachable_infinite(65 + 0 * inp.len(), inp);
-    // let x1 = count_reachable_infinite(65 + 1 * inp.len(), inp);
-    // let x2 = count_reachable_infinite(65 + 2 * inp.len(), inp);
-    // => f(65), f(65 + inp.len()), f(65 + inp.len() * 2))
-
-    // println!("f(65 + 0 * inp.len()) = {x0}");
-    // println!("f(65 + 1 * inp.len()) = {x1}");
-    // println!("f(65 + 2 * inp.len()) = {x2}");
+pub fn part2(inp: &[Vec<char>]) -> Result<usize, Overflow> {
+    // fitted from `part2_interpolated` on my input:
     // => f(x) = 3848 + 15276 * x + 15186 * x^2
 
-    let num_steps = 26_501_365usize / inp.len();
-    num_steps.pow(2) * 15186 + num_steps * 15276 + 3848
+    let x = 26_501_365usize / inp.len();
+    checked::sum([
+        checked::mul(&checked::mul(&x, &x)?, &15186)?,
+        checked::mul(&x, &15276)?,
+        3848,
+    ])
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const TEST_INPUT: &str = "...........\n\
-                              .....###.#.\n\
-                              .###.##..#.\n\
-                              ..#.#...#..\n\
-                              ....#.#....\n\
-                              .##..S####.\n\
-                              .##..#...#.\n\
-                              .......##..\n\
-                              .##.#.####.\n\
-                              .##..##.##.\n\
-                              ...........";
-
-    #[test]
-    fn test_p1() {
-        let gen = generate(TEST_INPUT);
-        let res = count_reachable(6, &gen);
-        assert_eq!(res, 16);
-    }
-
-    #[test]
-    fn test_p2() {
-        let gen = generate(TEST_INPUT);
-        let res = count_reachable_infinite(6, &gen);
-        assert_eq!(res, 16);
-
-        let gen = generate(TEST_INPUT);
-        let res = count_reachable_infinite(10, &gen);
-        assert_eq!(res, 50);
-
-        let gen = generate(TEST_INPUT);
-        let res = count_reachable_infinite(50, &gen);
-        assert_eq!(res, 1594);
-
-        let gen = generate(TEST_INPUT);
-        let res = count_reachable_infinite(100, &gen);
-        assert_eq!(res, 6536);
-    }
+#[aoc(day21, part2, interpolated)]
+pub fn part2_interpolated(inp: &[Vec<char>]) -> Result<usize, Box<dyn Error>> {
+    const STEPS: usize = 26_501_365;
+
+    // the start row and column are free, so the reachable area grows quadratically
+    // per crossed map: f(x) = reachable after (STEPS % len + x * len) steps
+    let len = inp.len();
+    let [f0, f1, f2] = [0, 1, 2].map(|x| count_reachable_infinite(STEPS % len + x * len, inp));
+
+    // Newton's forward differences
+    let x = STEPS / len;
+    // a map on which fewer plots are reachable the further you walk isn't one this solves
+    let not_growing = "the reachable area doesn't grow quadratically";
+    let first = checked::mul(&x, &f1.checked_sub(f0).ok_or(not_growing)?)?;
+    let second = checked::add(&f2, &f0)?
+        .checked_sub(checked::mul(&2, &f1)?)
+        .ok_or(not_growing)?;
+    let pairs = x * x.saturating_sub(1) / 2;
+
+    Ok(checked::sum([f0, first, checked::mul(&pairs, &second)?])?)
 }
@@ -1,8 +1,13 @@
+use crate::cache;
 use aoc_runner_derive::{aoc, aoc_generator};
+use aoc_utils::checked::{self, Overflow};
+use aoc_utils::parse::{Error, Source};
+use aoc_utils::progress::Progress;
 use itertools::Itertools;
-use parse_display_derive::{Display, FromStr};
+use parse_display_derive::Display;
+use serde::{Deserialize, Serialize};
 
-#[derive(Display, FromStr, PartialEq, Eq, Hash, Copy, Clone, Debug)]
+#[derive(Display, Serialize, Deserialize, PartialEq, Eq, Hash, Copy, Clone, Debug)]
 #[display("{x},{y},{z}")]
 pub struct Point {
     x: i64,
@@ -10,7 +15,7 @@ pub struct Point {
     z: i64,
 }
 
-#[derive(Display, FromStr, PartialEq, Eq, Hash, Copy, Clone, Debug)]
+#[derive(Display, Serialize, Deserialize, PartialEq, Eq, Hash, Copy, Clone, Debug)]
 #[display("{from}~{to}")]
 pub struct Brick {
     from: Point,
@@ -18,9 +23,30 @@ pub struct Brick {
 }
 
 #[aoc_generator(day22)]
-pub fn generate(inp: &str) -> Vec<Brick> {
+pub fn generate(inp: &str) -> Result<Vec<Brick>, Error> {
+    let source = Source::new(inp);
+
     inp.lines()
-        .map(|it| it.parse::<Brick>().expect("input"))
+        .map(|line| {
+            let [x1, y1, z1, x2, y2, z2] = source.integer_array(line)?;
+            // the collision checks expect each brick to go from its low to its high corner
+            if [x1, y1, z1].iter().any(|it| *it < 0) || x1 > x2 || y1 > y2 || z1 > z2 {
+                return Err(source.error(line, "expected a brick from its low to its high end"));
+            }
+
+            Ok(Brick {
+                from: Point {
+                    x: x1,
+                    y: y1,
+                    z: z1,
+                },
+                to: Point {
+                    x: x2,
+                    y: y2,
+                    z: z2,
+                },
+            })
+        })
         .collect()
 }
 
@@ -37,12 +63,14 @@ fn collides_with_any(cur: usize, next_pos: Brick, bricks: &[Brick]) -> bool {
     })
 }
 
-fn simulate_fall(bricks: &mut [Brick]) -> usize {
+// moves every brick that can fall down by one, returns how many did
+pub fn simulate_fall(bricks: &mut [Brick]) -> usize {
     let mut number_falling = 0;
 
     for i in 0..bricks.len() {
+        // the ground is at z = 0, and anything already below it stays put
         let cur = bricks[i];
-        if cur.from.z == 1 || cur.to.z == 1 {
+        if cur.from.z.min(cur.to.z) <= 1 {
             continue;
         }
 
@@ -59,15 +87,55 @@ fn simulate_fall(bricks: &mut [Brick]) -> usize {
     number_falling
 }
 
-fn fall_initial(inp: &[Brick]) -> Vec<Brick> {
-    let mut inp = inp
-        .iter()
+// lowest bricks first, so a single pass can move a whole stack
+pub fn sorted_by_height(inp: &[Brick]) -> Vec<Brick> {
+    inp.iter()
         .sorted_by_key(|it| it.from.z.min(it.to.z))
         .copied()
-        .collect_vec();
+        .collect()
+}
+
+// the stack seen along the y axis, top row first; bricks are lettered in input order
+pub fn side_view(bricks: &[Brick]) -> Vec<String> {
+    let max_x = bricks
+        .iter()
+        .map(|it| it.to.x.max(it.from.x))
+        .max()
+        .unwrap_or(0);
+    let max_z = bricks
+        .iter()
+        .map(|it| it.to.z.max(it.from.z))
+        .max()
+        .unwrap_or(0);
+
+    (1..=max_z)
+        .rev()
+        .map(|z| {
+            (0..=max_x)
+                .map(|x| {
+                    let mut hits = bricks.iter().positions(|it| {
+                        (it.from.x..=it.to.x).contains(&x) && (it.from.z..=it.to.z).contains(&z)
+                    });
+
+                    match (hits.next(), hits.next()) {
+                        (None, _) => '.',
+                        (Some(idx), None) => char::from(b'A' + (idx % 26) as u8),
+                        (Some(_), Some(_)) => '?',
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn settle(inp: &[Brick]) -> Vec<Brick> {
+    let _span = tracing::info_span!("initial_fall", bricks = inp.len()).entered();
+    let mut inp = sorted_by_height(inp);
 
-    loop {
-        if simulate_fall(&mut inp) == 0 {
+    for round in 1.. {
+        let falling = simulate_fall(&mut inp);
+        tracing::trace!(round, falling, "fall round");
+        if falling == 0 {
             break;
         }
     }
@@ -75,6 +143,11 @@ fn fall_initial(inp: &[Brick]) -> Vec<Brick> {
     inp
 }
 
+// both parts start from the settled stack, which takes a while to get to
+fn fall_initial(inp: &[Brick]) -> Vec<Brick> {
+    cache::cached(22, "settled", &inp.iter().join("\n"), |_| settle(inp))
+}
+
 #[aoc(day22, part1)]
 pub fn part1(inp: &[Brick]) -> usize {
     let inp = fall_initial(inp);
@@ -86,40 +159,24 @@ pub fn part1(inp: &[Brick]) -> usize {
     })
 }
 
-#[aoc(day22, part2)]
-pub fn part2(inp: &[Brick]) -> usize {
+// reports every brick once the chain reaction of removing it is known
+pub fn part2_with<P: Progress + ?Sized>(inp: &[Brick], progress: &P) -> Result<usize, Overflow> {
     let inp = fall_initial(inp);
+    progress.start(Some(inp.len()));
 
-    (0..inp.len()).fold(0, |acc, it| {
+    let result = (0..inp.len()).try_fold(0, |acc, it| {
         let mut rem = inp.clone();
         rem.remove(it);
-        acc + simulate_fall(&mut rem)
-    })
-}
+        let fallen = simulate_fall(&mut rem);
+        progress.advance(1);
+        checked::add(&acc, &fallen)
+    });
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const TEST_INPUT: &str = "1,0,1~1,2,1\n\
-                              0,0,2~2,0,2\n\
-                              0,2,3~2,2,3\n\
-                              0,0,4~0,2,4\n\
-                              2,0,5~2,2,5\n\
-                              0,1,6~2,1,6\n\
-                              1,1,8~1,1,9";
-
-    #[test]
-    fn test_p1() {
-        let gen = generate(TEST_INPUT);
-        let res = part1(&gen);
-        assert_eq!(res, 5);
-    }
+    progress.finish();
+    result
+}
 
-    #[test]
-    fn test_p2() {
-        let gen = generate(TEST_INPUT);
-        let res = part2(&gen);
-        assert_eq!(res, 7);
-    }
+#[aoc(day22, part2)]
+pub fn part2(inp: &[Brick]) -> Result<usize, Overflow> {
+    part2_with(inp, &())
 }
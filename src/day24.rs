@@ -1,12 +1,14 @@
 use aoc_runner_derive::{aoc, aoc_generator};
+use aoc_utils::checked::Overflow;
+use aoc_utils::parse::{Error, Source};
 use geo::{Coord, Line};
 use intersect2d::intersect;
 use itertools::Itertools;
-use parse_display_derive::{Display, FromStr};
+use parse_display_derive::Display;
 use std::ops::{Add, Mul, Sub};
 use z3::ast::Ast;
 
-#[derive(Display, FromStr, Copy, Clone, Debug)]
+#[derive(Display, Copy, Clone, Debug)]
 #[display("{x}, {y}, {z}")]
 pub struct Point3D {
     x: f64,
@@ -50,7 +52,7 @@ impl Mul<f64> for Point3D {
     }
 }
 
-#[derive(Display, FromStr, Copy, Clone, Debug)]
+#[derive(Display, Copy, Clone, Debug)]
 #[display("{position} @ {velocity}")]
 pub struct Hailstone {
     position: Point3D,
@@ -58,9 +60,28 @@ pub struct Hailstone {
 }
 
 #[aoc_generator(day24)]
-pub fn generate(inp: &str) -> Vec<Hailstone> {
+#[allow(clippy::cast_precision_loss)]
+pub fn generate(inp: &str) -> Result<Vec<Hailstone>, Error> {
+    let source = Source::new(inp);
+
     inp.lines()
-        .map(|it| it.parse::<Hailstone>().expect("input"))
+        .map(|line| {
+            // integers only, so no infinities or NaNs get into the intersections
+            let [px, py, pz, vx, vy, vz] =
+                source.integer_array::<i64, 6>(line)?.map(|it| it as f64);
+            Ok(Hailstone {
+                position: Point3D {
+                    x: px,
+                    y: py,
+                    z: pz,
+                },
+                velocity: Point3D {
+                    x: vx,
+                    y: vy,
+                    z: vz,
+                },
+            })
+        })
         .collect()
 }
 
@@ -73,8 +94,7 @@ pub fn part1(inp: &[Hailstone]) -> usize {
 }
 
 #[aoc(day24, part2)]
-#[allow(clippy::unwrap_used)]
-pub fn part2(inp: &[Hailstone]) -> i64 {
+pub fn part2(inp: &[Hailstone]) -> Result<i64, Box<dyn std::error::Error>> {
     let cfg = z3::Config::new();
     let ctx = z3::Context::new(&cfg);
     let solver = z3::Solver::new(&ctx);
@@ -114,18 +134,21 @@ pub fn part2(inp: &[Hailstone]) -> i64 {
         solver.assert(&lhs_z._eq(&rhs_z));
     }
 
-    assert_eq!(solver.check(), z3::SatResult::Sat);
-    if let Some(model) = solver.get_model() {
-        return model
-            .eval(&px0.add(py0).add(pz0), true)
-            .and_then(|it| it.as_i64())
-            .expect("solution");
+    match solver.check() {
+        z3::SatResult::Sat => {}
+        z3::SatResult::Unsat => return Err("no rock hits every hailstone".into()),
+        z3::SatResult::Unknown => return Err("z3 could not decide the input".into()),
     }
 
-    unreachable!("no solution found")
+    let model = solver.get_model().ok_or("z3 returned no model")?;
+    // z3 adds without bounds, the sum only has to fit once it's read back
+    let sum = model
+        .eval(&px0.add(py0).add(pz0), true)
+        .ok_or("z3 could not evaluate the sum")?;
+    Ok(sum.as_i64().ok_or(Overflow { op: "addition" })?)
 }
 
-fn count_collisions_in_boundary(min: f64, max: f64, stones: &[Hailstone]) -> usize {
+pub fn count_collisions_in_boundary(min: f64, max: f64, stones: &[Hailstone]) -> usize {
     // stretched line segment start, end inside [min, max]
     let v = stones
         .iter()
@@ -160,28 +183,3 @@ fn count_collisions_in_boundary(min: f64, max: f64, stones: &[Hailstone]) -> usi
 
     res
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const TEST_INPUT: &str = "19, 13, 30 @ -2, 1, -2\n\
-                              18, 19, 22 @ -1, -1, -2\n\
-                              20, 25, 34 @ -2, -2, -4\n\
-                              12, 31, 28 @ -1, -2, -1\n\
-                              20, 19, 15 @ 1, -5, -3";
-
-    #[test]
-    fn test_p1() {
-        let gen = generate(TEST_INPUT);
-        let res = count_collisions_in_boundary(7f64, 27f64, &gen);
-        assert_eq!(res, 2);
-    }
-
-    #[test]
-    fn test_p2() {
-        let gen = generate(TEST_INPUT);
-        let res = part2(&gen);
-        assert_eq!(res, 47);
-    }
-}
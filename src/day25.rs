@@ -1,130 +1,93 @@
 use aoc_runner_derive::{aoc, aoc_generator};
-use itertools::Itertools;
-use std::collections::{HashMap, HashSet};
+use aoc_utils::graph::Graph;
+use aoc_utils::label::Label;
+use aoc_utils::parse::{Error as ParseError, Source};
+use std::error::Error;
 
 #[derive(Clone, Debug)]
 pub struct NodeInfo {
-    id: String,
-    dests: Vec<String>,
+    id: Label,
+    dests: Vec<Label>,
 }
 
 #[aoc_generator(day25)]
-pub fn generate(inp: &str) -> Vec<NodeInfo> {
+pub fn generate(inp: &str) -> Result<Vec<NodeInfo>, ParseError> {
+    let source = Source::new(inp);
+    let label = |it: &str| Label::new(it).map_err(|e| source.error(it, e));
     let mut result = vec![];
 
     for line in inp.lines() {
-        let (id, targets) = line.split_once(':').expect("delimiter");
+        let (id, targets) = source.split_once(line, ":")?;
 
         let mut dests = vec![];
         for d in targets.split_ascii_whitespace() {
-            dests.push(d.trim().to_string());
+            dests.push(label(d)?);
         }
 
         result.push(NodeInfo {
-            id: id.trim().to_string(),
+            id: label(id.trim())?,
             dests,
         });
     }
 
-    result
+    Ok(result)
 }
 
-fn collect_node_mapping(inp: &[NodeInfo]) -> HashMap<String, HashSet<String>> {
-    let mut mapping = HashMap::new();
+fn collect_node_mapping(inp: &[NodeInfo]) -> Graph {
+    let mut graph = Graph::new();
 
     for ni in inp {
-        let v: &mut HashSet<String> = mapping.entry(ni.id.clone()).or_default();
+        let from = graph.add_label(ni.id);
         for dest in &ni.dests {
-            if !v.contains(dest) {
-                v.insert(dest.clone());
-            }
-        }
-
-        for dest in &ni.dests {
-            if !mapping.contains_key(dest) {
-                mapping.insert(dest.clone(), HashSet::new());
+            let to = graph.add_label(*dest);
+            if !graph.successors(from).contains(&to) {
+                graph.add_edge(from, to);
             }
         }
     }
 
-    mapping
+    graph
 }
 
-fn components_product(inp: &[NodeInfo], to_cut: &[(String, String)]) -> usize {
-    let mut mapping = collect_node_mapping(inp);
+pub fn components_product(
+    inp: &[NodeInfo],
+    to_cut: &[(Label, Label)],
+) -> Result<usize, Box<dyn Error>> {
+    let mut graph = collect_node_mapping(inp);
 
     for (from, to) in to_cut {
-        mapping.get_mut(from).expect("exists").remove(to);
+        let node = |label: &Label| {
+            graph
+                .id(label.as_str())
+                .ok_or_else(|| format!("no node {label}"))
+        };
+        let (from, to) = (node(from)?, node(to)?);
+        graph.remove_edge(from, to);
     }
 
-    let vertices = mapping.keys().cloned().collect_vec();
-
-    let comps = pathfinding::prelude::strongly_connected_components(&vertices, |it| {
-        let mut connections = vec![];
-
-        let outgoing = mapping
-            .get(it)
-            .map_or(vec![], |n| n.iter().cloned().collect_vec());
-        connections.extend_from_slice(&outgoing);
-
-        let incoming = mapping
-            .iter()
-            .filter(|&(_, v)| v.contains(it))
-            .map(|(k, _)| k)
-            .cloned()
-            .collect_vec();
-        connections.extend_from_slice(&incoming);
-
-        connections
-    });
+    let comps = graph.components();
+    if comps.len() != 2 {
+        return Err(format!("expected two components, found {}", comps.len()).into());
+    }
 
-    assert_eq!(comps.len(), 2);
+    Ok(comps.iter().map(Vec::len).product())
+}
 
-    comps.iter().map(Vec::len).product()
+// "a/b,c/d" => [(a, b), (c, d)]
+pub fn parse_cuts(inp: &str) -> Result<Vec<(Label, Label)>, ParseError> {
+    let source = Source::new(inp);
+    let label = |it: &str| Label::new(it).map_err(|e| source.error(it, e));
+
+    inp.split(',')
+        .map(|it| {
+            let (from, to) = source.split_once(it, "/")?;
+            Ok((label(from)?, label(to)?))
+        })
+        .collect()
 }
 
 #[aoc(day25, part1)]
-pub fn part1(inp: &[NodeInfo]) -> usize {
+pub fn part1(inp: &[NodeInfo]) -> Result<usize, Box<dyn Error>> {
     // graphviz and identify visually which to cut
-    components_product(
-        inp,
-        &[
-            ("ptq".to_string(), "fxn".to_string()),
-            ("fbd".to_string(), "lzd".to_string()),
-            ("szl".to_string(), "kcn".to_string()),
-        ],
-    )
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const TEST_INPUT: &str = "jqt: rhn xhk nvd\n\
-                              rsh: frs pzl lsr\n\
-                              xhk: hfx\n\
-                              cmg: qnr nvd lhk bvb\n\
-                              rhn: xhk bvb hfx\n\
-                              bvb: xhk hfx\n\
-                              pzl: lsr hfx nvd\n\
-                              qnr: nvd\n\
-                              ntq: jqt hfx bvb xhk\n\
-                              nvd: lhk\n\
-                              lsr: lhk\n\
-                              rzs: qnr cmg lsr rsh\n\
-                              frs: qnr lhk lsr";
-
-    #[test]
-    fn test_p1() {
-        let gen = generate(TEST_INPUT);
-        let res = components_product(
-            &gen,
-            &[
-                ("pzl".to_string(), "hfx".to_string()),
-                ("cmg".to_string(), "bvb".to_string()),
-                ("jqt".to_string(), "nvd".to_string()),
-            ],
-        );
-        assert_eq!(res, 54);
-    }
+    components_product(inp, &parse_cuts("ptq/fxn,fbd/lzd,szl/kcn")?)
 }
@@ -0,0 +1,222 @@
+use crate::day16::Beam;
+use crate::day20::Network;
+use crate::day22::Brick;
+use crate::{day14, day16, day20, day22};
+use aoc_utils::parse::Error;
+use std::collections::HashSet;
+
+// A day's intermediate state, stepped forward one unit at a time and drawn as text.
+pub trait Simulation {
+    // returns false once stepping doesn't change anything anymore
+    fn step(&mut self) -> bool;
+
+    fn steps(&self) -> usize;
+
+    fn view(&self) -> Vec<String>;
+
+    fn status(&self) -> String;
+}
+
+fn grid_view(grid: &[Vec<char>]) -> Vec<String> {
+    grid.iter().map(|it| it.iter().collect()).collect()
+}
+
+// day14: one step is a full spin cycle
+struct SpinCycles {
+    grid: Vec<Vec<char>>,
+    cycles: usize,
+}
+
+impl Simulation for SpinCycles {
+    fn step(&mut self) -> bool {
+        let before = self.grid.clone();
+        day14::simulate_round(&mut self.grid);
+        self.cycles += 1;
+
+        self.grid != before
+    }
+
+    fn steps(&self) -> usize {
+        self.cycles
+    }
+
+    fn view(&self) -> Vec<String> {
+        grid_view(&self.grid)
+    }
+
+    fn status(&self) -> String {
+        day14::calculate_load(&self.grid).map_or_else(|e| e.to_string(), |it| format!("load {it}"))
+    }
+}
+
+// day16: one step moves every beam by one tile
+struct Beams {
+    grid: Vec<Vec<char>>,
+    front: Vec<Beam>,
+    seen: HashSet<Beam>,
+    ticks: usize,
+}
+
+impl Beams {
+    fn energized(&self) -> HashSet<(usize, usize)> {
+        self.seen.iter().map(Beam::tile).collect()
+    }
+}
+
+impl Simulation for Beams {
+    fn step(&mut self) -> bool {
+        if self.front.is_empty() {
+            return false;
+        }
+
+        self.front = day16::advance_beams(&self.front, &self.grid, &mut self.seen);
+        self.ticks += 1;
+
+        true
+    }
+
+    fn steps(&self) -> usize {
+        self.ticks
+    }
+
+    fn view(&self) -> Vec<String> {
+        let energized = self.energized();
+
+        let mut grid = self.grid.clone();
+        for (row, col) in energized {
+            if grid[row][col] == '.' {
+                grid[row][col] = '#';
+            }
+        }
+
+        grid_view(&grid)
+    }
+
+    fn status(&self) -> String {
+        format!(
+            "{} beams, {} energized",
+            self.front.len(),
+            self.energized().len()
+        )
+    }
+}
+
+// day20: one step is a button press
+struct ButtonPresses {
+    network: Network,
+    presses: usize,
+    high: usize,
+    low: usize,
+}
+
+impl Simulation for ButtonPresses {
+    fn step(&mut self) -> bool {
+        let (high, low) = self.network.press_button();
+        self.presses += 1;
+        self.high += high;
+        self.low += low;
+
+        true
+    }
+
+    fn steps(&self) -> usize {
+        self.presses
+    }
+
+    fn view(&self) -> Vec<String> {
+        self.network.to_string().lines().map(String::from).collect()
+    }
+
+    fn status(&self) -> String {
+        format!("{} high, {} low pulses", self.high, self.low)
+    }
+}
+
+// day22: one step lets every brick fall by one
+struct Settling {
+    bricks: Vec<Brick>,
+    steps: usize,
+    falling: usize,
+}
+
+impl Simulation for Settling {
+    fn step(&mut self) -> bool {
+        self.falling = day22::simulate_fall(&mut self.bricks);
+        self.steps += 1;
+
+        self.falling > 0
+    }
+
+    fn steps(&self) -> usize {
+        self.steps
+    }
+
+    fn view(&self) -> Vec<String> {
+        day22::side_view(&self.bricks)
+    }
+
+    fn status(&self) -> String {
+        format!("{} bricks falling", self.falling)
+    }
+}
+
+// `None` for days without anything to step through
+pub fn simulation(day: u32, inp: &str) -> Result<Option<Box<dyn Simulation>>, Error> {
+    // cargo-aoc strips trailing newlines before handing the input to the generators
+    let inp = inp.trim_end_matches('\n');
+
+    let sim: Box<dyn Simulation> = match day {
+        14 => Box::new(SpinCycles {
+            grid: day14::generate(inp)?,
+            cycles: 0,
+        }),
+        16 => Box::new(Beams {
+            grid: day16::generate(inp)?,
+            front: vec![Beam::start()],
+            seen: HashSet::from([Beam::start()]),
+            ticks: 0,
+        }),
+        20 => Box::new(ButtonPresses {
+            network: day20::generate(inp)?,
+            presses: 0,
+            high: 0,
+            low: 0,
+        }),
+        22 => Box::new(Settling {
+            bricks: day22::sorted_by_height(&day22::generate(inp)?),
+            steps: 0,
+            falling: 0,
+        }),
+        _ => return Ok(None),
+    };
+
+    Ok(Some(sim))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_beams() {
+        let inp = include_str!("../examples/2023/day16/example.txt");
+        let mut sim = simulation(16, inp).expect("input").expect("simulation");
+
+        while sim.step() {}
+
+        assert_eq!(sim.status(), "0 beams, 46 energized");
+        assert_eq!(sim.view()[0], "#|###\\....");
+    }
+
+    #[test]
+    fn test_button_presses() {
+        let inp = include_str!("../examples/2023/day20/example.txt");
+        let mut sim = simulation(20, inp).expect("input").expect("simulation");
+
+        sim.step();
+        assert_eq!(sim.steps(), 1);
+        assert_eq!(sim.status(), "4 high, 8 low pulses");
+        assert!(simulation(1, inp).expect("input").is_none());
+        assert!(simulation(16, "x").is_err());
+    }
+}
@@ -2,11 +2,21 @@
 #![warn(nonstandard_style, rust_2018_idioms)]
 #![allow(
     clippy::implicit_hasher,
+    clippy::missing_errors_doc,
     clippy::similar_names,
     clippy::cast_possible_truncation,
     clippy::cast_sign_loss
 )]
 
+pub mod batch;
+pub mod cache;
+pub mod calibration;
+pub mod explore;
+pub mod registry;
+pub mod report;
+#[cfg(feature = "trace")]
+pub mod trace;
+
 mod day01;
 mod day02;
 mod day03;
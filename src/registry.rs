@@ -0,0 +1,554 @@
+use crate::{
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
+    day14, day15, day16, day17, day18, day19, day20, day21, day22, day23, day24, day25,
+};
+use aoc_utils::cancel::Token;
+use aoc_utils::progress::Progress;
+use aoc_utils::stats::{Counters, Stats};
+use itertools::Itertools;
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::panic::{self, AssertUnwindSafe};
+use std::str::FromStr;
+use std::time::Duration;
+
+pub const DEFAULT_NAME: &str = "default";
+
+pub type Answer = Result<String, Box<dyn Error>>;
+
+// Solutions either return their answer directly or a `Result` of it.
+pub trait IntoAnswer {
+    fn into_answer(self) -> Answer;
+}
+
+macro_rules! impl_into_answer {
+    ($($t:ty),*) => {
+        $(impl IntoAnswer for $t {
+            fn into_answer(self) -> Answer {
+                Ok(self.to_string())
+            }
+        })*
+    };
+}
+
+impl_into_answer!(u32, u64, u128, usize, i64, isize, String);
+
+impl<T: IntoAnswer, E: Into<Box<dyn Error>>> IntoAnswer for Result<T, E> {
+    fn into_answer(self) -> Answer {
+        self.map_err(Into::into)?.into_answer()
+    }
+}
+
+// Overrides for constants the solutions hard-code for the real input, e.g. the number
+// of steps on day 21. Unparsable values are treated like missing ones.
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct Params(BTreeMap<String, String>);
+
+impl Params {
+    pub fn insert(&mut self, key: &str, value: &str) {
+        self.0.insert(key.to_string(), value.to_string());
+    }
+
+    #[must_use]
+    pub fn get<T: FromStr>(&self, key: &str) -> Option<T> {
+        self.0.get(key).and_then(|it| it.parse().ok())
+    }
+}
+
+// What a run offers the parts: a token to stop early on, somewhere to report progress
+// and counters to fill in. Each part only takes what it supports, and gets `&()` for
+// what's left out, so its hot loops don't pay for reporting nobody asked for.
+#[derive(Copy, Clone)]
+pub struct Context<'a> {
+    pub token: &'a Token,
+    pub progress: Option<&'a dyn Progress>,
+    pub stats: Option<&'a dyn Stats>,
+}
+
+impl<'a> Context<'a> {
+    // neither reporting progress nor counting anything
+    #[must_use]
+    pub const fn new(token: &'a Token) -> Self {
+        Self {
+            token,
+            progress: None,
+            stats: None,
+        }
+    }
+}
+
+// Runs the generator and hands back the part, so both can be measured on their own.
+pub type Prepared = Box<dyn FnOnce(&Context<'_>) -> Answer>;
+
+// a generator that rejected its input leaves nothing to solve, only its error to report
+fn failed(e: impl Into<Box<dyn Error>>) -> Prepared {
+    let e = e.into();
+    Box::new(move |_| Err(e))
+}
+
+// what a part panicked with, to report it like any other error
+#[must_use]
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|it| (*it).to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "panicked".to_string())
+}
+
+// a panic only fails the row it happened in, with its message as the error
+pub fn caught<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(f))
+        .map_err(|e| format!("panicked: {}", panic_message(&*e)))
+}
+
+// Mirrors the `#[aoc(..)]` attributes, so solutions can be run without cargo-aoc.
+// Named alternatives are registered next to the default solution of their part.
+#[derive(Copy, Clone, Debug)]
+pub struct Solver {
+    pub day: u32,
+    pub part: u32,
+    pub name: &'static str,
+    build: fn(&str, &Params) -> Prepared,
+}
+
+impl Solver {
+    // the generator and the part each run in their own span
+    pub fn prepare(&self, inp: &str, params: &Params) -> Prepared {
+        let (day, part, name) = (self.day, self.part, self.name);
+
+        let solve =
+            tracing::info_span!("generate", day, part, name).in_scope(|| (self.build)(inp, params));
+
+        let span = tracing::info_span!("solve", day, part, name);
+        Box::new(move |ctx| span.in_scope(|| solve(ctx)))
+    }
+
+    pub fn solve(&self, inp: &str, params: &Params) -> Answer {
+        self.solve_with(inp, params, &Context::new(&Token::new()))
+    }
+
+    pub fn solve_with(&self, inp: &str, params: &Params, ctx: &Context<'_>) -> Answer {
+        self.prepare(inp, params)(ctx)
+    }
+}
+
+// calls `$solve` with the named `Context` fields, one at a time so each of progress and
+// stats becomes either the `&dyn` that was asked for or a `&()` that compiles away
+macro_rules! call_with {
+    ($solve:path, $gen:expr, $ctx:ident, [$($done:expr),*]) => {
+        $solve($gen, $($done),*)
+    };
+    ($solve:path, $gen:expr, $ctx:ident, [$($done:expr),*] token $(, $rest:ident)*) => {
+        call_with!($solve, $gen, $ctx, [$($done,)* $ctx.token] $($rest),*)
+    };
+    ($solve:path, $gen:expr, $ctx:ident, [$($done:expr),*] progress $(, $rest:ident)*) => {
+        match $ctx.progress {
+            Some(progress) => call_with!($solve, $gen, $ctx, [$($done,)* progress] $($rest),*),
+            None => call_with!($solve, $gen, $ctx, [$($done,)* &()] $($rest),*),
+        }
+    };
+    ($solve:path, $gen:expr, $ctx:ident, [$($done:expr),*] stats $(, $rest:ident)*) => {
+        match $ctx.stats {
+            Some(stats) => call_with!($solve, $gen, $ctx, [$($done,)* stats] $($rest),*),
+            None => call_with!($solve, $gen, $ctx, [$($done,)* &()] $($rest),*),
+        }
+    };
+}
+
+macro_rules! solver {
+    // `with(token, progress)` passes those fields of the `Context` after the input
+    ($day:literal, $part:literal, $gen:path => $solve:path, with($($arg:ident),+)) => {
+        Solver {
+            day: $day,
+            part: $part,
+            name: DEFAULT_NAME,
+            build: |inp, _| {
+                let gen = match $gen(inp) {
+                    Ok(gen) => gen,
+                    Err(e) => return failed(e),
+                };
+                Box::new(move |ctx| call_with!($solve, &gen, ctx, [] $($arg),+).into_answer())
+            },
+        }
+    };
+    ($day:literal, $part:literal, $name:literal, $gen:path => $solve:path) => {
+        Solver {
+            day: $day,
+            part: $part,
+            name: $name,
+            build: |inp, _| {
+                let gen = match $gen(inp) {
+                    Ok(gen) => gen,
+                    Err(e) => return failed(e),
+                };
+                Box::new(move |_| $solve(&gen).into_answer())
+            },
+        }
+    };
+    ($day:literal, $part:literal, $gen:path => |$inp:ident, $params:ident| $body:expr) => {
+        Solver {
+            day: $day,
+            part: $part,
+            name: DEFAULT_NAME,
+            build: |inp, params| {
+                let gen = match $gen(inp) {
+                    Ok(gen) => gen,
+                    Err(e) => return failed(e),
+                };
+                let $params = params.clone();
+                Box::new(move |_| {
+                    let $inp = &gen;
+                    $body.into_answer()
+                })
+            },
+        }
+    };
+    ($day:literal, $part:literal, $gen:path => $solve:path) => {
+        Solver {
+            day: $day,
+            part: $part,
+            name: DEFAULT_NAME,
+            build: |inp, _| {
+                let gen = match $gen(inp) {
+                    Ok(gen) => gen,
+                    Err(e) => return failed(e),
+                };
+                Box::new(move |_| $solve(&gen).into_answer())
+            },
+        }
+    };
+    // no generator, the part parses the input itself
+    ($day:literal, $part:literal, $solve:path) => {
+        Solver {
+            day: $day,
+            part: $part,
+            name: DEFAULT_NAME,
+            build: |inp, _| {
+                let inp = inp.to_string();
+                Box::new(move |_| $solve(&inp).into_answer())
+            },
+        }
+    };
+}
+
+#[must_use]
+pub fn solvers() -> Vec<Solver> {
+    vec![
+        solver!(1, 1, day01::part1),
+        solver!(1, 2, day01::part2),
+        solver!(2, 1, day02::generate => day02::part1),
+        solver!(2, 2, day02::generate => day02::part2),
+        solver!(3, 1, day03::generate => day03::part1),
+        solver!(3, 2, day03::generate => day03::part2),
+        solver!(4, 1, day04::generate => day04::part1),
+        solver!(4, 2, day04::generate => day04::part2),
+        solver!(5, 1, day05::generate => day05::part1),
+        solver!(5, 2, day05::generate => day05::part2),
+        solver!(6, 1, day06::generate => day06::part1),
+        solver!(6, 1, "binary_search", day06::generate => day06::part1_binary_search),
+        solver!(6, 2, day06::generate => day06::part2),
+        solver!(6, 2, "binary_search", day06::generate => day06::part2_binary_search),
+        solver!(7, 1, day07::generate => day07::part1),
+        solver!(7, 2, day07::generate => day07::part2),
+        solver!(8, 1, day08::generate => day08::part1_with, with(token)),
+        solver!(8, 2, day08::generate => day08::part2_with, with(token)),
+        solver!(9, 1, day09::generate => day09::part1),
+        solver!(9, 2, day09::generate => day09::part2),
+        solver!(10, 1, day10::generate => |inp, params| {
+            params.get("start").map_or_else(
+                || day10::part1(inp),
+                |start| day10::run_p1_with_start_as(start, inp),
+            )
+        }),
+        solver!(10, 2, day10::generate => |inp, params| {
+            params.get("start").map_or_else(
+                || day10::part2(inp),
+                |start| day10::run_p2_with_start_as(start, inp),
+            )
+        }),
+        solver!(11, 1, day11::generate => day11::part1),
+        solver!(11, 2, day11::generate => |inp, params| {
+            params.get("expansion").map_or_else(
+                || day11::part2(inp),
+                |factor| day11::shortest_paths_after_expansion(factor, inp),
+            )
+        }),
+        solver!(12, 1, day12::generate => day12::part1_with, with(stats)),
+        solver!(12, 2, day12::generate => day12::part2_with, with(progress, stats)),
+        solver!(13, 1, day13::generate => day13::part1),
+        solver!(13, 2, day13::generate => day13::part2),
+        solver!(14, 1, day14::generate => day14::part1),
+        solver!(14, 2, day14::generate => day14::part2_with, with(stats)),
+        solver!(15, 1, day15::generate => day15::part1),
+        solver!(15, 2, day15::generate => day15::part2),
+        solver!(16, 1, day16::generate => day16::part1),
+        solver!(16, 2, day16::generate => day16::part2_with, with(progress)),
+        solver!(17, 1, day17::generate => day17::part1_with, with(stats)),
+        solver!(17, 2, day17::generate => day17::part2_with, with(stats)),
+        solver!(18, 1, day18::generate_p1 => day18::part1),
+        solver!(18, 2, day18::generate_p2 => day18::part2),
+        solver!(19, 1, day19::generate => day19::part1),
+        solver!(19, 2, day19::generate => day19::part2),
+        solver!(20, 1, day20::generate => day20::part1_with, with(stats)),
+        solver!(20, 2, day20::generate => day20::part2_with, with(token, stats)),
+        solver!(21, 1, day21::generate => |inp, params| {
+            params.get("steps").map_or_else(
+                || day21::part1(inp),
+                |steps| day21::count_reachable(steps, inp),
+            )
+        }),
+        solver!(21, 2, day21::generate => |inp, params| {
+            params.get("steps").map_or_else(
+                || day21::part2(inp),
+                |steps| Ok(day21::count_reachable_infinite(steps, inp)),
+            )
+        }),
+        solver!(21, 2, "interpolated", day21::generate => day21::part2_interpolated),
+        solver!(22, 1, day22::generate => day22::part1),
+        solver!(22, 2, day22::generate => day22::part2_with, with(progress)),
+        solver!(23, 1, day23::generate => day23::part1_with, with(token, progress, stats)),
+        solver!(23, 2, day23::generate => day23::part2_with, with(token, progress, stats)),
+        solver!(24, 1, day24::generate => |inp, params| {
+            match (params.get("min"), params.get("max")) {
+                (Some(min), Some(max)) => day24::count_collisions_in_boundary(min, max, inp),
+                _ => day24::part1(inp),
+            }
+        }),
+        solver!(24, 2, day24::generate => day24::part2),
+        solver!(25, 1, day25::generate => |inp, params| {
+            params.get::<String>("cuts").map_or_else(
+                || day25::part1(inp),
+                |cuts| day25::components_product(inp, &day25::parse_cuts(&cuts)?),
+            )
+        }),
+    ]
+}
+
+pub fn solvers_for(day: u32, part: u32) -> impl Iterator<Item = Solver> {
+    solvers()
+        .into_iter()
+        .filter(move |it| it.day == day && it.part == part)
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CrossCheck {
+    pub day: u32,
+    pub part: u32,
+    pub answers: Vec<(&'static str, Result<String, String>)>,
+    // what each implementation counted, if asked for
+    pub stats: Vec<(&'static str, Vec<(&'static str, usize)>)>,
+}
+
+impl CrossCheck {
+    #[must_use]
+    pub fn agrees(&self) -> bool {
+        self.answers.iter().map(|(_, answer)| answer).all_equal()
+    }
+}
+
+// Runs every registered implementation of every part of `day` on `inp`.
+#[must_use]
+pub fn crosscheck(day: u32, inp: &str) -> Vec<CrossCheck> {
+    crosscheck_with(day, inp, None, false)
+}
+
+#[must_use]
+pub fn crosscheck_with(
+    day: u32,
+    inp: &str,
+    progress: Option<&dyn Progress>,
+    collect_stats: bool,
+) -> Vec<CrossCheck> {
+    crosscheck_within(day, inp, progress, collect_stats, None)
+}
+
+// What the fuzz targets run: `crosscheck` with a time limit per implementation. One that
+// runs out of time is left out of the answers, since that isn't a finding.
+#[must_use]
+pub fn fuzz(day: u32, inp: &str) -> Vec<CrossCheck> {
+    crosscheck_within(day, inp, None, false, Some(FUZZ_TIMEOUT))
+}
+
+const FUZZ_TIMEOUT: Duration = Duration::from_millis(250);
+
+fn crosscheck_within(
+    day: u32,
+    inp: &str,
+    progress: Option<&dyn Progress>,
+    collect_stats: bool,
+    timeout: Option<Duration>,
+) -> Vec<CrossCheck> {
+    // cargo-aoc strips trailing newlines before handing the input to the generators
+    let inp = inp.trim_end_matches('\n');
+
+    solvers()
+        .into_iter()
+        .filter(|it| it.day == day)
+        .chunk_by(|it| it.part)
+        .into_iter()
+        .map(|(part, solvers)| {
+            let mut answers = vec![];
+            let mut stats = vec![];
+
+            for solver in solvers {
+                let counters = Counters::new();
+                let token = timeout.map_or_else(Token::new, Token::with_timeout);
+                let ctx = Context {
+                    token: &token,
+                    progress,
+                    stats: collect_stats.then_some(&counters as &dyn Stats),
+                };
+
+                let answer = solver.solve_with(inp, &Params::default(), &ctx);
+                if timeout.is_some() && token.check().is_err() {
+                    continue;
+                }
+
+                answers.push((solver.name, answer.map_err(|e| e.to_string())));
+                if collect_stats {
+                    stats.push((solver.name, counters.to_vec()));
+                }
+            }
+
+            CrossCheck {
+                day,
+                part,
+                answers,
+                stats,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use regex::Regex;
+    use std::path::Path;
+
+    #[test]
+    fn test_one_default_per_part() {
+        let solvers = solvers();
+        let defaults = solvers
+            .iter()
+            .filter(|it| it.name == DEFAULT_NAME)
+            .map(|it| (it.day, it.part))
+            .collect_vec();
+
+        assert!(defaults.iter().all_unique());
+        assert!(solvers
+            .iter()
+            .all(|it| defaults.contains(&(it.day, it.part))));
+    }
+
+    // the list above is kept by hand, so check it against what cargo-aoc runs
+    #[test]
+    fn test_solvers_match_aoc_attributes() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        let lib = std::fs::read_to_string(dir.join("lib.rs")).expect("lib.rs");
+        let attribute = Regex::new(r"#\[aoc\(day(\d+), part(\d)(?:, (\w+))?\)\]").expect("pattern");
+
+        let mut expected = vec![];
+        for module in lib.lines().filter_map(|it| it.strip_prefix("mod ")) {
+            let module = module.trim_end_matches(';');
+            let source = std::fs::read_to_string(dir.join(format!("{module}.rs"))).expect("day");
+            for caps in attribute.captures_iter(&source) {
+                expected.push((
+                    caps[1].parse::<u32>().expect("day"),
+                    caps[2].parse::<u32>().expect("part"),
+                    caps.get(3)
+                        .map_or(DEFAULT_NAME, |it| it.as_str())
+                        .to_string(),
+                ));
+            }
+        }
+
+        let registered = solvers()
+            .iter()
+            .map(|it| (it.day, it.part, it.name.to_string()))
+            .sorted()
+            .collect_vec();
+        expected.sort();
+        assert_eq!(registered, expected);
+    }
+
+    #[test]
+    fn test_crosscheck() {
+        let checks = crosscheck(6, "Time:      7  15   30\nDistance:  9  40  200\n");
+        assert_eq!(checks.len(), 2);
+        assert!(checks.iter().all(CrossCheck::agrees));
+        assert_eq!(
+            checks[1].answers,
+            [
+                ("default", Ok("71503".to_string())),
+                ("binary_search", Ok("71503".to_string()))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_malformed_inputs() {
+        // what the fuzz targets run into first; rejecting them is fine, panicking isn't
+        for inp in ["", "x", "#", "1", "1 2\n3", ":", "a -> b", "{}\n\n{}"] {
+            for day in 1..=25 {
+                for check in crosscheck(day, inp) {
+                    assert!(!check.answers.is_empty(), "day{day} on {inp:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_fuzz_stops_on_time() {
+        // never reaches a target node
+        let checks = fuzz(8, "L\n\nAAA = (AAA, AAA)");
+        assert_eq!(checks.len(), 2);
+        assert!(checks.iter().all(|it| it.answers.is_empty()));
+
+        assert_eq!(
+            fuzz(6, "Time: 7\nDistance: 9")[0].answers[0].1,
+            Ok("4".into())
+        );
+    }
+
+    #[test]
+    fn test_caught() {
+        assert_eq!(caught(|| 1), Ok(1));
+        assert_eq!(
+            caught(|| -> u32 { panic!("no start") }),
+            Err("panicked: no start".into())
+        );
+    }
+
+    #[test]
+    fn test_time_budget() {
+        let inp = include_str!("../examples/2023/day08/example.txt");
+        let solver = solvers()
+            .into_iter()
+            .find(|it| it.day == 8 && it.part == 1)
+            .expect("day08 part1");
+
+        let token = Token::with_timeout(Duration::ZERO);
+        let answer = solver.solve_with(inp, &Params::default(), &Context::new(&token));
+        assert_eq!(
+            answer.map_err(|e| e.to_string()),
+            Err("timed out (progress: 0)".to_string())
+        );
+        assert_eq!(solver.solve(inp, &Params::default()).ok(), Some("2".into()));
+    }
+
+    #[test]
+    fn test_stats() {
+        let inp = include_str!("../examples/2023/day14/example.txt");
+        let checks = crosscheck_with(14, inp, None, true);
+
+        assert_eq!(checks[0].stats, [("default", vec![])]);
+        assert_eq!(
+            checks[1].stats,
+            [("default", vec![("cycle_length", 7), ("cycle_start", 3)])]
+        );
+        assert!(crosscheck(14, inp).iter().all(|it| it.stats.is_empty()));
+    }
+}
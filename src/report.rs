@@ -0,0 +1,171 @@
+use crate::registry::{self, caught, Context, Params};
+use aoc_utils::cancel::Token;
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+pub const CSV_HEADER: &str = "day,part,name,checksum,answer,duration_us,error";
+
+// One solver run, meant to be collected across commits and diffed.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Record {
+    pub day: u32,
+    pub part: u32,
+    pub name: &'static str,
+    pub checksum: String,
+    pub answer: Option<String>,
+    // `None` when timings are left out, so runs on different machines compare equal
+    pub duration: Option<Duration>,
+    pub error: Option<String>,
+}
+
+// FNV-1a, so the checksum stays the same across Rust versions and platforms
+#[must_use]
+pub fn checksum(inp: &str) -> String {
+    let hash = inp.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+
+    format!("{hash:016x}")
+}
+
+fn json_string(s: &str) -> String {
+    let mut result = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(result, "\\u{:04x}", u32::from(c));
+            }
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+impl Record {
+    // a single line, so NDJSON is just one of these per line
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let optional = |it: Option<&str>| it.map_or_else(|| "null".to_string(), json_string);
+
+        format!(
+            "{{\"day\":{},\"part\":{},\"name\":{},\"checksum\":{},\"answer\":{},\"duration_us\":{},\"error\":{}}}",
+            self.day,
+            self.part,
+            json_string(self.name),
+            json_string(&self.checksum),
+            optional(self.answer.as_deref()),
+            self.duration
+                .map_or_else(|| "null".to_string(), |it| it.as_micros().to_string()),
+            optional(self.error.as_deref()),
+        )
+    }
+
+    #[must_use]
+    pub fn to_csv(&self) -> String {
+        [
+            self.day.to_string(),
+            self.part.to_string(),
+            csv_field(self.name),
+            self.checksum.clone(),
+            csv_field(self.answer.as_deref().unwrap_or_default()),
+            self.duration
+                .map(|it| it.as_micros().to_string())
+                .unwrap_or_default(),
+            csv_field(self.error.as_deref().unwrap_or_default()),
+        ]
+        .join(",")
+    }
+}
+
+// Runs every registered implementation of every part of `day` on `inp`, in registry order.
+#[must_use]
+pub fn run(day: u32, inp: &str, timings: bool) -> Vec<Record> {
+    // cargo-aoc strips trailing newlines before handing the input to the generators
+    let inp = inp.trim_end_matches('\n');
+    let checksum = checksum(inp);
+
+    registry::solvers()
+        .into_iter()
+        .filter(|it| it.day == day)
+        .map(|solver| {
+            let start = Instant::now();
+            let answer = caught(|| solver.prepare(inp, &Params::default())).and_then(|part| {
+                caught(|| part(&Context::new(&Token::new())))?.map_err(|e| e.to_string())
+            });
+            let duration = start.elapsed();
+
+            let (answer, error) = match answer {
+                Ok(answer) => (Some(answer), None),
+                Err(e) => (None, Some(e)),
+            };
+
+            Record {
+                day,
+                part: solver.part,
+                name: solver.name,
+                checksum: checksum.clone(),
+                answer,
+                duration: timings.then_some(duration),
+                error,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_formats() {
+        let record = Record {
+            day: 1,
+            part: 2,
+            name: "default",
+            checksum: checksum(""),
+            answer: None,
+            duration: Some(Duration::from_millis(3)),
+            error: Some("bad \"input\", line 1".to_string()),
+        };
+
+        assert_eq!(
+            record.to_json(),
+            r#"{"day":1,"part":2,"name":"default","checksum":"cbf29ce484222325","answer":null,"duration_us":3000,"error":"bad \"input\", line 1"}"#
+        );
+        assert_eq!(
+            record.to_csv(),
+            r#"1,2,default,cbf29ce484222325,,3000,"bad ""input"", line 1""#
+        );
+    }
+
+    #[test]
+    fn test_run_is_stable() {
+        let inp = "Time:      7  15   30\nDistance:  9  40  200\n";
+        let records = run(6, inp, false);
+
+        assert_eq!(records, run(6, inp.trim_end(), false));
+        assert_eq!(
+            records.iter().map(Record::to_csv).collect::<Vec<_>>(),
+            [
+                "6,1,default,73a4eba03c18da8a,288,,",
+                "6,1,binary_search,73a4eba03c18da8a,288,,",
+                "6,2,default,73a4eba03c18da8a,71503,,",
+                "6,2,binary_search,73a4eba03c18da8a,71503,,"
+            ]
+        );
+    }
+}
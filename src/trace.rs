@@ -0,0 +1,48 @@
+use std::error::Error;
+use std::fs::File;
+use std::io::BufWriter;
+use tracing_flame::{FlameLayer, FlushGuard};
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer};
+
+// an env-filter directive, e.g. `debug` or `aoc_2023::day12=trace`
+pub const LOG_VAR: &str = "AOC_LOG";
+
+// where to write folded stacks, for `inferno-flamegraph` and friends
+pub const FLAME_VAR: &str = "AOC_FLAME";
+
+// Flushes the flame data when dropped, so keep it alive until the end of `main`.
+pub type Guard = Option<FlushGuard<BufWriter<File>>>;
+
+// Installs a subscriber for whatever of `AOC_LOG` and `AOC_FLAME` is set. Logs go to
+// stderr, with the duration of each span once it closes.
+pub fn init() -> Result<Guard, Box<dyn Error>> {
+    let log = std::env::var(LOG_VAR)
+        .ok()
+        .map(|directive| {
+            Ok::<_, Box<dyn Error>>(
+                tracing_subscriber::fmt::layer()
+                    .with_writer(std::io::stderr)
+                    .with_span_events(FmtSpan::CLOSE)
+                    .with_filter(EnvFilter::try_new(directive)?),
+            )
+        })
+        .transpose()?;
+
+    let (flame, guard) = match std::env::var_os(FLAME_VAR) {
+        Some(path) => {
+            let (layer, guard) = FlameLayer::with_file(path)?;
+            (Some(layer), Some(guard))
+        }
+        None => (None, None),
+    };
+
+    tracing_subscriber::registry()
+        .with(log)
+        .with(flame)
+        .try_init()?;
+
+    Ok(guard)
+}
@@ -0,0 +1,107 @@
+use aoc_2023::registry::{self, Params, DEFAULT_NAME};
+use std::fs;
+use std::path::Path;
+
+// Every `examples/2023/dayNN/manifest.txt` lists one example per line:
+//
+//     <file> <part> <expected answer> [<param>=<value> ...]
+//
+// `#` starts a comment. Params are handed to the solvers, except for `solver=<name>`,
+// which restricts the run to that solver instead of every registered one for the part.
+struct Example {
+    file: String,
+    part: u32,
+    expected: String,
+    solver: Option<String>,
+    params: Params,
+}
+
+fn parse_manifest(manifest: &str) -> Vec<Example> {
+    manifest
+        .lines()
+        .map(|it| it.split('#').next().expect("line").trim())
+        .filter(|it| !it.is_empty())
+        .map(|line| {
+            let mut fields = line.split_ascii_whitespace();
+            let file = fields.next().expect("file").to_string();
+            let part = fields.next().expect("part").parse().expect("number");
+            let expected = fields.next().expect("expected answer").to_string();
+
+            let mut solver = None;
+            let mut params = Params::default();
+            for field in fields {
+                match field.split_once('=').expect("key=value") {
+                    ("solver", name) => solver = Some(name.to_string()),
+                    (key, value) => params.insert(key, value),
+                }
+            }
+
+            Example {
+                file,
+                part,
+                expected,
+                solver,
+                params,
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn test_examples() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("examples/2023");
+
+    let mut days = fs::read_dir(&root)
+        .expect("examples directory")
+        .map(|it| it.expect("entry").path())
+        .collect::<Vec<_>>();
+    days.sort();
+
+    let mut failures = vec![];
+    let mut checked = 0;
+
+    for dir in days {
+        let day = dir
+            .file_name()
+            .and_then(|it| it.to_str())
+            .and_then(|it| it.strip_prefix("day"))
+            .and_then(|it| it.parse::<u32>().ok())
+            .expect("dayNN directory");
+
+        let manifest = fs::read_to_string(dir.join("manifest.txt")).expect("manifest");
+        for example in parse_manifest(&manifest) {
+            let inp = fs::read_to_string(dir.join(&example.file)).expect("example file");
+            // cargo-aoc strips trailing newlines before handing the input to the generators
+            let inp = inp.trim_end_matches('\n');
+
+            let solvers = registry::solvers_for(day, example.part)
+                .filter(|it| example.solver.as_deref().is_none_or(|name| it.name == name))
+                .collect::<Vec<_>>();
+            if solvers.is_empty() {
+                failures.push(format!("day{day:02}/{}: no solver", example.file));
+            }
+
+            for solver in solvers {
+                checked += 1;
+
+                let answer = solver
+                    .solve(inp, &example.params)
+                    .map_err(|e| e.to_string());
+                if answer.as_deref() != Ok(example.expected.as_str()) {
+                    let name = if solver.name == DEFAULT_NAME {
+                        String::new()
+                    } else {
+                        format!(" ({})", solver.name)
+                    };
+                    failures.push(format!(
+                        "day{day:02}/{} part{}{name}: expected {}, got {answer:?}",
+                        example.file, example.part, example.expected
+                    ));
+                }
+            }
+        }
+    }
+
+    assert!(checked > 0);
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}